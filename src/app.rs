@@ -1,11 +1,135 @@
+use base64::prelude::*;
 use eframe::CreationContext;
-use egui_snarl::{Snarl, ui::SnarlStyle};
+use egui_snarl::{NodeId, Snarl, ui::SnarlStyle};
 
-use crate::node::{Node, NodeViewer};
+use crate::group::Group;
+use crate::help::node_descriptions;
+use crate::node::{Data, Node, NodeViewer, data_color};
+use crate::share;
+use crate::stats::GraphStats;
+use crate::tab::Tab;
+use crate::transform::{Encoding, Transformer};
+
+const MINIMAP_SIZE: f32 = 160.0;
+
+/// A key that creates a node under the mouse cursor
+struct Shortcut {
+    key: egui::Key,
+    name: &'static str,
+    node: fn() -> Node,
+}
+
+const SHORTCUTS: [Shortcut; 9] = [
+    Shortcut {
+        key: egui::Key::I,
+        name: "Input",
+        node: || Node::input(String::new()),
+    },
+    Shortcut {
+        key: egui::Key::S,
+        name: "Split",
+        node: || {
+            Node::transform(
+                Transformer::Split {
+                    pattern: String::new(),
+                },
+                Data::List(Vec::new()),
+            )
+        },
+    },
+    Shortcut {
+        key: egui::Key::J,
+        name: "Join",
+        node: || {
+            Node::transform(
+                Transformer::Join {
+                    separator: String::new(),
+                    prefix: String::new(),
+                    suffix: String::new(),
+                },
+                Data::Text(String::new()),
+            )
+        },
+    },
+    Shortcut {
+        key: egui::Key::F,
+        name: "Find",
+        node: || {
+            Node::transform(
+                Transformer::Find {
+                    pattern: String::new(),
+                    flatten: false,
+                },
+                Data::List(Vec::new()),
+            )
+        },
+    },
+    Shortcut {
+        key: egui::Key::R,
+        name: "Replace",
+        node: || {
+            Node::transform(
+                Transformer::Replace {
+                    pattern: String::new(),
+                    replacer: String::new(),
+                },
+                Data::Text(String::new()),
+            )
+        },
+    },
+    Shortcut {
+        key: egui::Key::E,
+        name: "Base64 Encode",
+        node: || {
+            Node::transform(
+                Transformer::Encode {
+                    encoding: Encoding::Base64,
+                },
+                Data::Text(String::new()),
+            )
+        },
+    },
+    Shortcut {
+        key: egui::Key::D,
+        name: "Base64 Decode",
+        node: || {
+            Node::transform(
+                Transformer::Decode {
+                    encoding: Encoding::Base64,
+                },
+                Data::Text(String::new()),
+            )
+        },
+    },
+    Shortcut {
+        key: egui::Key::U,
+        name: "Uppercase",
+        node: || Node::transform(Transformer::Uppercase, Data::Text(String::new())),
+    },
+    Shortcut {
+        key: egui::Key::L,
+        name: "Lowercase",
+        node: || Node::transform(Transformer::Lowercase, Data::Text(String::new())),
+    },
+];
 
 pub struct App {
+    /// Graph of the current tab, moved out of `tabs` while it's shown
     snarl: Snarl<Node>,
+    tabs: Vec<Tab>,
+    current_tab: usize,
     style: SnarlStyle,
+    viewer: NodeViewer,
+    paste_error: Option<String>,
+    show_help: bool,
+    show_minimap: bool,
+    share_link: String,
+    /// Outcome of the last export or import
+    share_status: Option<Result<String, String>>,
+    search_open: bool,
+    search_query: String,
+    /// Index into the nodes matching the query of the one to show
+    search_index: usize,
 }
 
 const fn default_snarl_style() -> SnarlStyle {
@@ -30,22 +154,590 @@ impl App {
                 .unwrap_or_else(default_snarl_style)
         });
 
-        Self { snarl, style }
+        let font_scale = cx
+            .storage
+            .and_then(|storage| storage.get_string("font_scale"))
+            .and_then(|font_scale| font_scale.parse().ok())
+            .unwrap_or(1.0);
+
+        let show_minimap = cx
+            .storage
+            .and_then(|storage| storage.get_string("show_minimap"))
+            .and_then(|show_minimap| show_minimap.parse().ok())
+            .unwrap_or(true);
+
+        let groups = cx
+            .storage
+            .and_then(|storage| storage.get_string("groups"))
+            .and_then(|groups| serde_json::from_str(&groups).ok())
+            .unwrap_or_default();
+
+        // Before tabs, the one graph was stored on its own
+        let tabs = cx
+            .storage
+            .and_then(|storage| storage.get_string("tabs"))
+            .and_then(|tabs| serde_json::from_str(&tabs).ok())
+            .filter(|tabs: &Vec<Tab>| !tabs.is_empty())
+            .unwrap_or_else(|| {
+                vec![Tab {
+                    name: "Graph".into(),
+                    snarl,
+                    groups,
+                }]
+            });
+
+        let current_tab = cx
+            .storage
+            .and_then(|storage| storage.get_string("current_tab"))
+            .and_then(|current_tab| current_tab.parse().ok())
+            .filter(|&current_tab| current_tab < tabs.len())
+            .unwrap_or(0);
+
+        let mut app = Self {
+            snarl: Snarl::new(),
+            tabs,
+            current_tab,
+            style,
+            viewer: NodeViewer {
+                font_scale,
+                ..Default::default()
+            },
+            paste_error: None,
+            show_help: false,
+            show_minimap,
+            share_link: String::new(),
+            share_status: None,
+            search_open: false,
+            search_query: String::new(),
+            search_index: 0,
+        };
+        app.load_tab();
+        app
+    }
+
+    /// Puts the shown graph back into its tab
+    fn stash_tab(&mut self) {
+        let tab = &mut self.tabs[self.current_tab];
+        tab.snarl = std::mem::take(&mut self.snarl);
+        tab.groups = std::mem::take(&mut self.viewer.groups);
+    }
+
+    /// Takes the graph of the current tab out to show it
+    fn load_tab(&mut self) {
+        let tab = &mut self.tabs[self.current_tab];
+        self.snarl = std::mem::take(&mut tab.snarl);
+        self.viewer.groups = std::mem::take(&mut tab.groups);
+        self.viewer.node_rects.clear();
+        self.viewer.highlighted = None;
+        self.search_index = 0;
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        self.stash_tab();
+        self.current_tab = index;
+        self.load_tab();
+    }
+
+    fn show_tabs(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Tabs")
+            .default_open(false)
+            .anchor(egui::Align2::LEFT_TOP, [8.0, 128.0])
+            .show(ctx, |ui| {
+                let mut switch_to = None;
+                for (i, tab) in self.tabs.iter_mut().enumerate() {
+                    if i == self.current_tab {
+                        ui.text_edit_singleline(&mut tab.name);
+                    } else if ui.selectable_label(false, &tab.name).clicked() {
+                        switch_to = Some(i);
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Duplicate tab").clicked() {
+                        self.stash_tab();
+                        let copy = self.tabs[self.current_tab].duplicate();
+                        self.tabs.push(copy);
+                        self.current_tab = self.tabs.len() - 1;
+                        self.load_tab();
+                    }
+                    if ui
+                        .add_enabled(self.tabs.len() > 1, egui::Button::new("Close tab"))
+                        .clicked()
+                    {
+                        self.tabs.remove(self.current_tab);
+                        self.current_tab = self.current_tab.min(self.tabs.len() - 1);
+                        self.load_tab();
+                    }
+                });
+                if let Some(i) = switch_to {
+                    self.switch_tab(i);
+                }
+            });
+    }
+
+    fn show_search(&mut self, ctx: &egui::Context) {
+        let search_shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::F);
+        let focus = ctx.input_mut(|i| i.consume_shortcut(&search_shortcut));
+        if focus {
+            self.search_open = true;
+        }
+
+        let matches: Vec<NodeId> = if self.search_query.is_empty() {
+            Vec::new()
+        } else {
+            self.snarl
+                .node_ids()
+                .filter(|(_, node)| node.matches(&self.search_query))
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        let mut step = None;
+        egui::Window::new("Search")
+            .open(&mut self.search_open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 8.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.search_query)
+                            .hint_text("text, settings or output"),
+                    );
+                    if focus {
+                        response.request_focus();
+                    }
+                    if response.changed() {
+                        self.search_index = 0;
+                        step = Some(0);
+                    }
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        step = Some(1);
+                        response.request_focus();
+                    }
+                    if ui.button("⏶").clicked() {
+                        step = Some(-1);
+                    }
+                    if ui.button("⏷").clicked() {
+                        step = Some(1);
+                    }
+                    if matches.is_empty() {
+                        ui.weak("no matches");
+                    } else {
+                        ui.label(format!("{} of {}", self.search_index + 1, matches.len()));
+                    }
+                });
+            });
+
+        if !self.search_open || matches.is_empty() {
+            self.viewer.highlighted = None;
+            self.search_index = 0;
+            return;
+        }
+        self.search_index = match step {
+            Some(step) => {
+                (self.search_index as isize + step).rem_euclid(matches.len() as isize) as usize
+            }
+            None => self.search_index.min(matches.len() - 1),
+        };
+        let node = matches[self.search_index];
+        self.viewer.highlighted = Some(node);
+        if step.is_some() {
+            self.center_on(node);
+        }
+    }
+
+    fn center_on(&mut self, node: NodeId) {
+        if let Some(rect) = self.viewer.node_rects.get(&node) {
+            self.pan_to(rect.center());
+        }
+    }
+
+    /// Brings a graph position to the middle of the view. Snarl doesn't let us move the view, so
+    /// this moves all nodes instead
+    fn pan_to(&mut self, pos: egui::Pos2) {
+        let Some(viewport) = &self.viewer.viewport else {
+            return;
+        };
+        let delta = viewport.screen_pos_to_graph(viewport.rect.center()) - pos;
+        for info in self.snarl.nodes_info_mut() {
+            info.pos += delta;
+            if let Some(locked) = info.value.locked() {
+                info.value.set_locked(Some(locked + delta));
+            }
+        }
+        for rect in self.viewer.node_rects.values_mut() {
+            *rect = rect.translate(delta);
+        }
+    }
+
+    fn show_minimap(&mut self, ctx: &egui::Context) {
+        let Some(viewport) = &self.viewer.viewport else {
+            return;
+        };
+        let view = egui::Rect::from_min_max(
+            viewport.screen_pos_to_graph(viewport.rect.min),
+            viewport.screen_pos_to_graph(viewport.rect.max),
+        );
+        let bounds = self
+            .viewer
+            .node_rects
+            .values()
+            .fold(view, |bounds, rect| bounds.union(*rect));
+
+        let mut jump = None;
+        egui::Area::new(egui::Id::new("minimap"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-8.0, -8.0])
+            .show(ctx, |ui| {
+                egui::Frame::window(ui.style()).show(ui, |ui| {
+                    let size = MINIMAP_SIZE * (bounds.size() / bounds.size().max_elem());
+                    let (response, painter) =
+                        ui.allocate_painter(size, egui::Sense::click_and_drag());
+                    let rect = response.rect;
+                    let to_minimap = |pos: egui::Pos2| {
+                        rect.min + (pos - bounds.min) / bounds.size() * rect.size()
+                    };
+                    let to_graph = |pos: egui::Pos2| {
+                        bounds.min + (pos - rect.min) / rect.size() * bounds.size()
+                    };
+                    let visuals = ui.visuals();
+                    for node in self.viewer.node_rects.values() {
+                        painter.rect_filled(
+                            egui::Rect::from_min_max(to_minimap(node.min), to_minimap(node.max)),
+                            1.0,
+                            visuals.weak_text_color(),
+                        );
+                    }
+                    painter.rect_stroke(
+                        egui::Rect::from_min_max(to_minimap(view.min), to_minimap(view.max)),
+                        1.0,
+                        egui::Stroke::new(1.0, visuals.strong_text_color()),
+                        egui::StrokeKind::Inside,
+                    );
+                    if response.is_pointer_button_down_on()
+                        && let Some(pos) = response.interact_pointer_pos()
+                    {
+                        jump = Some(to_graph(pos));
+                    }
+                });
+            });
+        if let Some(pos) = jump {
+            self.pan_to(pos);
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::F1) || i.key_pressed(egui::Key::Questionmark)) {
+            self.show_help = !self.show_help;
+        }
+        let Some(viewport) = &self.viewer.viewport else {
+            return;
+        };
+        let Some(pointer) = ctx
+            .pointer_hover_pos()
+            .filter(|pos| viewport.rect.contains(*pos))
+        else {
+            return;
+        };
+        for shortcut in SHORTCUTS {
+            if ctx.input(|i| i.modifiers.is_none() && i.key_pressed(shortcut.key)) {
+                self.snarl
+                    .insert_node(viewport.screen_pos_to_graph(pointer), (shortcut.node)());
+            }
+        }
     }
+
+    /// Group labels double as handles for dragging the whole group around
+    fn show_group_labels(&mut self, ctx: &egui::Context) {
+        let Some(viewport) = &self.viewer.viewport else {
+            return;
+        };
+        for (i, group) in self.viewer.groups.iter().enumerate() {
+            let Some(rect) = group.rect(&self.viewer.node_rects) else {
+                continue;
+            };
+            let pos = viewport.graph_pos_to_screen(rect.min) + egui::vec2(8.0, 4.0);
+            let response = egui::Area::new(egui::Id::new("group_label").with(i))
+                .fixed_pos(pos)
+                .show(ctx, |ui| {
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(&group.label).strong())
+                            .selectable(false)
+                            .sense(egui::Sense::drag()),
+                    )
+                    .on_hover_cursor(egui::CursorIcon::Grab)
+                })
+                .inner;
+            if response.dragged() {
+                let delta = viewport.screen_vec_to_graph(response.drag_delta());
+                for node in &group.nodes {
+                    if let Some(info) = self.snarl.get_node_info_mut(*node) {
+                        info.pos += delta;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Reads an image from the clipboard, and returns the Base64 of it as a PNG
+fn clipboard_image_base64() -> Result<String, String> {
+    let image = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_image())
+        .map_err(|err| match err {
+            arboard::Error::ContentNotAvailable => "There's no image in the clipboard".into(),
+            err => err.to_string(),
+        })?;
+
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&image.bytes))
+        .map_err(|err| err.to_string())?;
+
+    Ok(BASE64_STANDARD.encode(png))
+}
+
+fn show_help(ui: &mut egui::Ui) {
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        ui.heading("Keyboard shortcuts");
+        egui::Grid::new("help_shortcuts").show(ui, |ui| {
+            ui.monospace("F1 / ?");
+            ui.label("Toggle this help");
+            ui.end_row();
+            for shortcut in SHORTCUTS {
+                ui.monospace(shortcut.key.name());
+                ui.label(format!("Add {} under the mouse", shortcut.name));
+                ui.end_row();
+            }
+        });
+
+        ui.heading("Pin colors");
+        egui::Grid::new("help_colors").show(ui, |ui| {
+            for (data, meaning) in [
+                (Data::Text(String::new()), "Text"),
+                (Data::List(Vec::new()), "List"),
+                (Data::Bytes(Vec::new()), "Bytes that aren't valid UTF-8"),
+                (Data::Error(String::new()), "Error"),
+            ] {
+                ui.colored_label(data_color(&data), "⏺");
+                ui.label(meaning);
+                ui.end_row();
+            }
+        });
+
+        ui.heading("Nodes");
+        egui::Grid::new("help_nodes").striped(true).show(ui, |ui| {
+            for (name, description) in node_descriptions() {
+                ui.strong(name);
+                ui.add(egui::Label::new(description).wrap());
+                ui.end_row();
+            }
+        });
+    });
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let mut selected = Vec::new();
         egui::CentralPanel::default().show(ctx, |ui| {
-            self.snarl.show(&mut NodeViewer, &self.style, "snarl", ui);
+            self.snarl.show(&mut self.viewer, &self.style, "snarl", ui);
+            selected = Snarl::<Node>::get_selected_nodes("snarl", ui);
         });
+
+        // Forget nodes that were removed
+        self.viewer
+            .node_rects
+            .retain(|node, _| self.snarl.get_node(*node).is_some());
+        for group in &mut self.viewer.groups {
+            group
+                .nodes
+                .retain(|node| self.snarl.get_node(*node).is_some());
+        }
+
+        self.show_group_labels(ctx);
+        self.show_tabs(ctx);
+        self.handle_shortcuts(ctx);
+        self.show_search(ctx);
+        if self.show_minimap {
+            self.show_minimap(ctx);
+        }
+
+        if let Some(pos) = self.viewer.paste_image_at.take() {
+            match clipboard_image_base64() {
+                Ok(base64) => {
+                    self.snarl.insert_node(pos, Node::input(base64));
+                }
+                Err(err) => self.paste_error = Some(err),
+            }
+        }
+        if let Some(err) = &self.paste_error {
+            let mut open = true;
+            egui::Window::new("Can't paste image")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| ui.label(err));
+            if !open {
+                self.paste_error = None;
+            }
+        }
+
+        egui::Window::new("Settings")
+            .default_open(false)
+            .anchor(egui::Align2::LEFT_TOP, [8.0, 8.0])
+            .show(ctx, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut self.viewer.font_scale, 0.5..=3.0)
+                        .text("Node font size"),
+                );
+                ui.checkbox(&mut self.show_minimap, "Minimap");
+                ui.toggle_value(&mut self.show_help, "Help (F1)");
+            });
+
+        egui::Window::new("Groups")
+            .default_open(false)
+            .anchor(egui::Align2::LEFT_TOP, [8.0, 48.0])
+            .show(ctx, |ui| {
+                let has_selection = !selected.is_empty();
+                if ui
+                    .add_enabled(has_selection, egui::Button::new("Group selected nodes"))
+                    .on_disabled_hover_text("Shift-drag on the canvas to select nodes")
+                    .clicked()
+                {
+                    self.viewer.groups.push(Group {
+                        label: "Group".into(),
+                        nodes: selected.clone(),
+                    });
+                }
+                let mut removed = None;
+                for (i, group) in self.viewer.groups.iter_mut().enumerate() {
+                    ui.separator();
+                    ui.text_edit_singleline(&mut group.label);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(has_selection, egui::Button::new("Add selected"))
+                            .clicked()
+                        {
+                            for node in &selected {
+                                if !group.nodes.contains(node) {
+                                    group.nodes.push(*node);
+                                }
+                            }
+                        }
+                        if ui
+                            .add_enabled(has_selection, egui::Button::new("Remove selected"))
+                            .clicked()
+                        {
+                            group.nodes.retain(|node| !selected.contains(node));
+                        }
+                        if ui.button("Delete").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = removed {
+                    self.viewer.groups.remove(i);
+                }
+            });
+
+        egui::Window::new("Help")
+            .open(&mut self.show_help)
+            .default_width(420.0)
+            .default_height(480.0)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .default_pos(ctx.screen_rect().center())
+            .show(ctx, show_help);
+
+        egui::Window::new("Share")
+            .default_open(false)
+            .anchor(egui::Align2::LEFT_TOP, [8.0, 88.0])
+            .show(ctx, |ui| {
+                if ui.button("Copy graph link").clicked() {
+                    self.share_status = Some(share::export(&self.snarl).map(|link| {
+                        ui.ctx().copy_text(link);
+                        "Copied to the clipboard".into()
+                    }));
+                }
+                ui.separator();
+                ui.add(egui::TextEdit::singleline(&mut self.share_link).hint_text("graph link"));
+                if ui
+                    .add_enabled(
+                        !self.share_link.is_empty(),
+                        egui::Button::new("Import, replacing this graph"),
+                    )
+                    .clicked()
+                {
+                    self.share_status = Some(share::import(&self.share_link).map(|snarl| {
+                        self.snarl = snarl;
+                        self.viewer.groups.clear();
+                        self.share_link.clear();
+                        "Imported".into()
+                    }));
+                }
+                match &self.share_status {
+                    Some(Ok(status)) => {
+                        ui.weak(status);
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                    None => (),
+                }
+            });
+
+        egui::Window::new("Statistics")
+            .default_open(false)
+            .anchor(egui::Align2::RIGHT_TOP, [-8.0, 8.0])
+            .show(ctx, |ui| {
+                let stats = GraphStats::new(&self.snarl);
+                egui::Grid::new("stats").show(ui, |ui| {
+                    ui.label("Nodes");
+                    ui.label(stats.nodes.to_string());
+                    ui.end_row();
+                    ui.label("Connections");
+                    ui.label(stats.connections.to_string());
+                    ui.end_row();
+                    ui.label("Depth");
+                    ui.label(stats.depth.to_string());
+                    ui.end_row();
+                });
+                ui.separator();
+                egui::Grid::new("stats_kinds").show(ui, |ui| {
+                    for (kind, count) in &stats.kinds {
+                        ui.label(kind);
+                        ui.label(count.to_string());
+                        ui.end_row();
+                    }
+                });
+            });
+
+        // Snarl has no hook to veto drags, so undo any movement of locked nodes
+        for node in self.snarl.nodes_info_mut() {
+            if let Some(pos) = node.value.locked() {
+                node.pos = pos;
+            }
+        }
     }
 
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        let snarl = serde_json::to_string(&self.snarl).unwrap();
-        storage.set_string("snarl", snarl);
+        self.stash_tab();
+        let tabs = serde_json::to_string(&self.tabs).unwrap();
+        storage.set_string("tabs", tabs);
+        storage.set_string("current_tab", self.current_tab.to_string());
+        self.load_tab();
 
         let style = serde_json::to_string(&self.style).unwrap();
         storage.set_string("style", style);
+
+        storage.set_string("font_scale", self.viewer.font_scale.to_string());
+        storage.set_string("show_minimap", self.show_minimap.to_string());
     }
 }
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+
+use crate::node::Data;
+
+/// Like a [`Transformer`](crate::transform::Transformer), but takes several inputs
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+pub enum Combiner {
+    Anagrams,
+    /// Alternates elements of both lists, appending the rest of the longer one
+    Interleave,
+    /// Recovers the shortest repeating key that turns the plaintext's letters into the ciphertext's
+    VigenereKey,
+    /// How alike two texts are, as a percentage based on their Levenshtein distance
+    Similarity,
+    /// Union, intersection or difference of two lists, without duplicates and in the order
+    /// elements first appear
+    SetOperation {
+        op: SetOp,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub enum SetOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl Combiner {
+    pub fn input_names(&self) -> &'static [&'static str] {
+        match self {
+            Combiner::Anagrams => &["target", "wordlist"],
+            Combiner::Interleave => &["first", "second"],
+            Combiner::VigenereKey => &["plaintext", "ciphertext"],
+            Combiner::Similarity => &["first", "second"],
+            Combiner::SetOperation { .. } => &["first", "second"],
+        }
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.input_names().len()
+    }
+
+    pub fn combine(&self, inputs: &[Data]) -> Data {
+        match self {
+            Combiner::Anagrams => match inputs {
+                [Data::Text(target), wordlist] => {
+                    let words = match wordlist {
+                        Data::Text(text) => text.lines().map(|l| Data::Text(l.into())).collect(),
+                        Data::List(list) => list.clone(),
+                        _ => return Data::Error("Input error".into()),
+                    };
+                    let target = anagram_signature(target);
+                    Data::List(
+                        words
+                            .into_iter()
+                            .filter(
+                                |w| matches!(w, Data::Text(w) if anagram_signature(w) == target),
+                            )
+                            .collect(),
+                    )
+                }
+                _ => Data::Error("Input error".into()),
+            },
+            Combiner::Interleave => match inputs {
+                [Data::List(first), Data::List(second)] => {
+                    let mut interleaved = Vec::with_capacity(first.len() + second.len());
+                    for i in 0..first.len().max(second.len()) {
+                        interleaved.extend(first.get(i).cloned());
+                        interleaved.extend(second.get(i).cloned());
+                    }
+                    Data::List(interleaved)
+                }
+                _ => Data::Error("Input error".into()),
+            },
+            Combiner::VigenereKey => match inputs {
+                [Data::Text(plaintext), Data::Text(ciphertext)] => {
+                    let letters = |text: &str| -> Vec<u8> {
+                        text.bytes()
+                            .filter(u8::is_ascii_alphabetic)
+                            .map(|b| b.to_ascii_uppercase() - b'A')
+                            .collect()
+                    };
+                    let (plaintext, ciphertext) = (letters(plaintext), letters(ciphertext));
+                    if plaintext.len() != ciphertext.len() {
+                        return Data::Error("Plaintext and ciphertext differ in length".into());
+                    }
+                    let key: Vec<u8> = plaintext
+                        .iter()
+                        .zip(&ciphertext)
+                        .map(|(p, c)| (c + 26 - p) % 26 + b'A')
+                        .collect();
+                    let period = (1..=key.len())
+                        .find(|&period| (period..key.len()).all(|i| key[i] == key[i - period]))
+                        .unwrap_or(0);
+                    Data::Text(String::from_utf8_lossy(&key[..period]).into())
+                }
+                _ => Data::Error("Input error".into()),
+            },
+            Combiner::Similarity => match inputs {
+                [Data::Text(first), Data::Text(second)] => Data::Text(format!(
+                    "{:.1}",
+                    strsim::normalized_levenshtein(first, second) * 100.0
+                )),
+                _ => Data::Error("Input error".into()),
+            },
+            Combiner::SetOperation { op } => match inputs {
+                [Data::List(first), Data::List(second)] => {
+                    let candidates = match op {
+                        SetOp::Union => first.iter().chain(second).collect::<Vec<_>>(),
+                        SetOp::Intersection => {
+                            first.iter().filter(|d| second.contains(d)).collect()
+                        }
+                        SetOp::Difference => first.iter().filter(|d| !second.contains(d)).collect(),
+                    };
+                    let mut result: Vec<Data> = Vec::new();
+                    for d in candidates {
+                        if !result.contains(d) {
+                            result.push(d.clone());
+                        }
+                    }
+                    Data::List(result)
+                }
+                _ => Data::Error("Input error".into()),
+            },
+        }
+    }
+}
+
+fn anagram_signature(word: &str) -> Vec<char> {
+    let mut chars: Vec<char> = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    chars.sort_unstable();
+    chars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anagrams() {
+        let wordlist = Data::List(vec![
+            Data::Text("enlist".into()),
+            Data::Text("google".into()),
+            Data::Text("Inlets".into()),
+            Data::Text("banana".into()),
+            Data::Text("silent".into()),
+        ]);
+
+        test_combiner(
+            &Combiner::Anagrams,
+            &[Data::Text("listen".into()), wordlist],
+            Data::List(vec![
+                Data::Text("enlist".into()),
+                Data::Text("Inlets".into()),
+                Data::Text("silent".into()),
+            ]),
+        );
+
+        test_combiner(
+            &Combiner::Anagrams,
+            &[Data::Text("act".into()), Data::Text("cat\ndog\ntac".into())],
+            Data::List(vec![Data::Text("cat".into()), Data::Text("tac".into())]),
+        );
+    }
+
+    #[test]
+    fn test_interleave() {
+        test_combiner(
+            &Combiner::Interleave,
+            &[
+                Data::List(vec![
+                    Data::Text("a".into()),
+                    Data::Text("c".into()),
+                    Data::Text("e".into()),
+                ]),
+                Data::List(vec![Data::Text("b".into()), Data::Text("d".into())]),
+            ],
+            Data::List(vec![
+                Data::Text("a".into()),
+                Data::Text("b".into()),
+                Data::Text("c".into()),
+                Data::Text("d".into()),
+                Data::Text("e".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_vigenere_key() {
+        test_combiner(
+            &Combiner::VigenereKey,
+            &[
+                Data::Text("Attack at dawn".into()),
+                Data::Text("KXRKGI KX BKAL".into()),
+            ],
+            Data::Text("KEY".into()),
+        );
+    }
+
+    #[test]
+    fn test_similarity() {
+        test_combiner(
+            &Combiner::Similarity,
+            &[Data::Text("puzzle".into()), Data::Text("puzzle".into())],
+            Data::Text("100.0".into()),
+        );
+        test_combiner(
+            &Combiner::Similarity,
+            &[Data::Text("puzzle".into()), Data::Text("puzzles".into())],
+            Data::Text("85.7".into()),
+        );
+        test_combiner(
+            &Combiner::Similarity,
+            &[Data::Text("abc".into()), Data::Text("xyz".into())],
+            Data::Text("0.0".into()),
+        );
+    }
+
+    #[test]
+    fn test_set_operation() {
+        let list =
+            |items: &[&str]| Data::List(items.iter().map(|i| Data::Text(i.to_string())).collect());
+        let inputs = [list(&["a", "b", "c"]), list(&["b", "c", "d"])];
+        test_combiner(
+            &Combiner::SetOperation {
+                op: SetOp::Intersection,
+            },
+            &inputs,
+            list(&["b", "c"]),
+        );
+        test_combiner(
+            &Combiner::SetOperation { op: SetOp::Union },
+            &inputs,
+            list(&["a", "b", "c", "d"]),
+        );
+        test_combiner(
+            &Combiner::SetOperation {
+                op: SetOp::Difference,
+            },
+            &inputs,
+            list(&["a"]),
+        );
+    }
+
+    fn test_combiner(combiner: &Combiner, inputs: &[Data], expected_output: Data) {
+        assert_eq!(combiner.combine(inputs), expected_output);
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use egui::{Rect, vec2};
+use egui_snarl::NodeId;
+use serde::{Deserialize, Serialize};
+
+const PADDING: f32 = 16.0;
+const LABEL_HEIGHT: f32 = 24.0;
+
+/// A labelled frame drawn behind a set of nodes, to organize the canvas
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Group {
+    pub label: String,
+    pub nodes: Vec<NodeId>,
+}
+
+impl Group {
+    /// Graph-space frame around the group's nodes, leaving room for the label on top
+    pub fn rect(&self, node_rects: &HashMap<NodeId, Rect>) -> Option<Rect> {
+        self.nodes
+            .iter()
+            .filter_map(|node| node_rects.get(node))
+            .copied()
+            .reduce(Rect::union)
+            .map(|rect| {
+                Rect::from_min_max(
+                    rect.min - vec2(PADDING, PADDING + LABEL_HEIGHT),
+                    rect.max + vec2(PADDING, PADDING),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::pos2;
+
+    use super::*;
+
+    #[test]
+    fn test_group_rect() {
+        let node_rects = HashMap::from([
+            (
+                NodeId(0),
+                Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0)),
+            ),
+            (
+                NodeId(1),
+                Rect::from_min_max(pos2(50.0, 20.0), pos2(60.0, 30.0)),
+            ),
+            (
+                NodeId(2),
+                Rect::from_min_max(pos2(500.0, 500.0), pos2(600.0, 600.0)),
+            ),
+        ]);
+        let group = Group {
+            label: "Group".into(),
+            nodes: vec![NodeId(0), NodeId(1), NodeId(3)],
+        };
+
+        assert_eq!(
+            group.rect(&node_rects),
+            Some(Rect::from_min_max(
+                pos2(-PADDING, -PADDING - LABEL_HEIGHT),
+                pos2(60.0 + PADDING, 30.0 + PADDING)
+            ))
+        );
+    }
+}
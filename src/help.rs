@@ -0,0 +1,36 @@
+/// The README doubles as the in-app reference, so the two can't drift apart
+const README: &str = include_str!("../README.md");
+
+/// Name and description of every node listed in the README
+pub fn node_descriptions() -> Vec<(&'static str, String)> {
+    let start = README.find("# Transformers").unwrap_or(0);
+    let end = README.find("\n---").unwrap_or(README.len());
+    README[start..end]
+        .lines()
+        .filter_map(|line| line.strip_prefix("- **"))
+        .filter_map(|line| {
+            let (name, rest) = line.split_once("**")?;
+            let (_, description) = rest.split_once(" - ")?;
+            Some((name, description.replace("**", "").replace('*', "")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_descriptions() {
+        let descriptions = node_descriptions();
+        assert_eq!(
+            descriptions[0],
+            (
+                "Split",
+                "turns Texts into Lists of Texts split by Pattern".to_string()
+            )
+        );
+        assert!(descriptions.iter().any(|(name, _)| *name == "Anagrams"));
+        assert!(descriptions.iter().any(|(name, _)| *name == "Route"));
+    }
+}
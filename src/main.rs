@@ -1,7 +1,13 @@
 use app::App;
 
 mod app;
+mod combine;
+mod group;
+mod help;
 mod node;
+mod share;
+mod stats;
+mod tab;
 mod transform;
 
 fn main() -> eframe::Result<()> {
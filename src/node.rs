@@ -1,28 +1,253 @@
-use egui::{Color32, Ui};
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use egui::{Color32, Painter, Pos2, Rect, Stroke, StrokeKind, Style, Ui};
 use egui_snarl::{
-    Snarl,
-    ui::{PinInfo, SnarlViewer},
+    InPinId, NodeId, OutPinId, Snarl,
+    ui::{AnyPins, BackgroundPattern, PinInfo, SnarlStyle, SnarlViewer, Viewport},
 };
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::transform::{Encoding, Transformer};
+use crate::combine::{Combiner, SetOp};
+use crate::group::Group;
+use crate::transform::{
+    AcrosticPos, BinFmt, CharsetName, ChecksumAlgo, ConfigFmt, DurationFmt, Encoding, EntityKind,
+    HashAlgorithm, IpFormat, MAX_SEQUENCE_LEN, MathFunc, MdKind, SeqKind, Transformer,
+};
 
 #[derive(Clone, Deserialize, Serialize)]
 pub enum Node {
     Input {
         text: String,
+        #[serde(default)]
+        locked: Option<Pos2>,
     },
     Transform {
         transformer: Transformer,
         data: Data,
+        #[serde(default)]
+        locked: Option<Pos2>,
+        /// Passes the input through unchanged instead of transforming it
+        #[serde(default)]
+        bypassed: bool,
+        #[serde(skip)]
+        last_run: Option<LastRun<Data, Transformer>>,
+    },
+    Combine {
+        combiner: Combiner,
+        data: Data,
+        #[serde(default)]
+        locked: Option<Pos2>,
+        #[serde(skip)]
+        last_run: Option<LastRun<Option<Vec<Data>>, Combiner>>,
+    },
+    /// Sends its input to the first output if `pattern` matches it, and to the second otherwise
+    Route {
+        pattern: String,
+        data: Data,
+        #[serde(default)]
+        locked: Option<Pos2>,
+    },
+    /// Gathers the data of every connected input into a list, always offering one spare input
+    Collect {
+        data: Data,
+        inputs: usize,
+        #[serde(default)]
+        locked: Option<Pos2>,
     },
+    /// Passes its input through until it's captured, then keeps emitting the captured data
+    Snapshot {
+        data: Data,
+        #[serde(default)]
+        captured: Option<Data>,
+        #[serde(default)]
+        locked: Option<Pos2>,
+    },
+}
+
+/// The input and settings a node's data was last computed from, and how long that took
+#[derive(Clone)]
+pub struct LastRun<I, S> {
+    input: I,
+    settings: S,
+    pub elapsed: Duration,
+}
+
+impl<I: Clone + PartialEq, S: Clone + PartialEq> LastRun<I, S> {
+    /// Recomputes and times `data`, unless it was already computed from the same input and settings
+    fn rerun(
+        last_run: &mut Option<Self>,
+        input: &I,
+        settings: &S,
+        data: &mut Data,
+        compute: impl FnOnce() -> Data,
+    ) {
+        if last_run
+            .as_ref()
+            .is_some_and(|run| run.input == *input && run.settings == *settings)
+        {
+            return;
+        }
+        let start = Instant::now();
+        *data = compute();
+        *last_run = Some(LastRun {
+            input: input.clone(),
+            settings: settings.clone(),
+            elapsed: start.elapsed(),
+        });
+    }
 }
 
 impl Node {
+    pub fn input(text: String) -> Self {
+        Node::Input { text, locked: None }
+    }
+
+    pub fn transform(transformer: Transformer, data: Data) -> Self {
+        Node::Transform {
+            transformer,
+            data,
+            locked: None,
+            bypassed: false,
+            last_run: None,
+        }
+    }
+
+    pub fn combine(combiner: Combiner, data: Data) -> Self {
+        Node::Combine {
+            combiner,
+            data,
+            locked: None,
+            last_run: None,
+        }
+    }
+
+    pub fn route(pattern: String) -> Self {
+        Node::Route {
+            pattern,
+            data: Data::Text(String::new()),
+            locked: None,
+        }
+    }
+
+    pub fn collect() -> Self {
+        Node::Collect {
+            data: Data::List(Vec::new()),
+            inputs: 1,
+            locked: None,
+        }
+    }
+
+    pub fn snapshot() -> Self {
+        Node::Snapshot {
+            data: Data::Text(String::new()),
+            captured: None,
+            locked: None,
+        }
+    }
+
     pub fn data(&self) -> Data {
         match self {
-            Node::Input { text } => Data::Text(text.into()),
-            Node::Transform { data, .. } => data.clone(),
+            Node::Input { text, .. } => Data::Text(text.into()),
+            Node::Transform { data, .. }
+            | Node::Combine { data, .. }
+            | Node::Route { data, .. }
+            | Node::Collect { data, .. } => data.clone(),
+            Node::Snapshot { data, captured, .. } => captured.as_ref().unwrap_or(data).clone(),
+        }
+    }
+
+    /// Data going out of the given output pin
+    pub fn output(&self, output: usize) -> Data {
+        let Node::Route { pattern, data, .. } = self else {
+            return self.data();
+        };
+        let Ok(re) = Regex::new(pattern) else {
+            return Data::Error("Invalid pattern".into());
+        };
+        match route_matches(&re, data) {
+            Some(matched) if matched == (output == 0) => data.clone(),
+            Some(_) => Data::Text(String::new()),
+            None => Data::Error("Input error".into()),
+        }
+    }
+
+    /// Recomputes a transform or route node's output from its input
+    pub fn evaluate(&mut self, input: &Data) {
+        match self {
+            Node::Transform {
+                transformer,
+                data,
+                bypassed,
+                last_run,
+                ..
+            } => {
+                if *bypassed {
+                    *data = input.clone();
+                    // Whatever was computed before is gone
+                    *last_run = None;
+                } else {
+                    LastRun::rerun(last_run, input, transformer, data, || {
+                        transformer.transform(input)
+                    });
+                }
+            }
+            Node::Route { data, .. } | Node::Snapshot { data, .. } => *data = input.clone(),
+            _ => (),
+        }
+    }
+
+    /// Makes a snapshot node hold on to its current input
+    pub fn capture(&mut self) {
+        if let Node::Snapshot { data, captured, .. } = self {
+            *captured = Some(data.clone());
+        }
+    }
+
+    /// Makes a snapshot node follow its input again
+    pub fn release(&mut self) {
+        if let Node::Snapshot { captured, .. } = self {
+            *captured = None;
+        }
+    }
+
+    /// Whether the query appears in the node's text, settings or output, ignoring case
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let settings = match self {
+            Node::Input { text, .. } => text.clone(),
+            Node::Transform { transformer, .. } => {
+                serde_json::to_string(transformer).unwrap_or_default()
+            }
+            Node::Combine { combiner, .. } => serde_json::to_string(combiner).unwrap_or_default(),
+            Node::Route { pattern, .. } => pattern.clone(),
+            Node::Collect { .. } | Node::Snapshot { .. } => String::new(),
+        };
+        settings.to_lowercase().contains(&query)
+            || format!("{:?}", self.data()).to_lowercase().contains(&query)
+    }
+
+    /// Position the node is pinned to, if it's locked in place
+    pub fn locked(&self) -> Option<Pos2> {
+        match self {
+            Node::Input { locked, .. }
+            | Node::Transform { locked, .. }
+            | Node::Combine { locked, .. }
+            | Node::Route { locked, .. }
+            | Node::Collect { locked, .. }
+            | Node::Snapshot { locked, .. } => *locked,
+        }
+    }
+
+    pub fn set_locked(&mut self, pos: Option<Pos2>) {
+        match self {
+            Node::Input { locked, .. }
+            | Node::Transform { locked, .. }
+            | Node::Combine { locked, .. }
+            | Node::Route { locked, .. }
+            | Node::Collect { locked, .. }
+            | Node::Snapshot { locked, .. } => *locked = pos,
         }
     }
 }
@@ -31,14 +256,25 @@ impl Node {
 pub enum Data {
     Text(String),
     List(Vec<Data>),
+    /// Raw bytes that aren't valid UTF-8
+    Bytes(Vec<u8>),
     Error(String),
 }
 
 impl Data {
+    /// Wraps the bytes as [`Data::Text`] if they're valid UTF-8, and as [`Data::Bytes`] otherwise
+    pub fn from_bytes(bytes: Vec<u8>) -> Data {
+        match String::from_utf8(bytes) {
+            Ok(text) => Data::Text(text),
+            Err(err) => Data::Bytes(err.into_bytes()),
+        }
+    }
+
     pub fn max_str_len(&self) -> usize {
         match self {
             Data::Text(text) => text.len(),
             Data::List(data_vec) => data_vec.iter().map(|d| d.max_str_len()).max().unwrap_or(0),
+            Data::Bytes(bytes) => bytes.len(),
             Data::Error(text) => text.len(),
         }
     }
@@ -49,12 +285,41 @@ impl std::fmt::Debug for Data {
         match self {
             Data::Text(text) => write!(f, "{text}"),
             Data::List(data_vec) => write!(f, "{data_vec:?}"),
+            Data::Bytes(bytes) => {
+                let hex: Vec<String> = bytes.iter().map(|b| format!("{b:02x}")).collect();
+                write!(f, "{}", hex.join(" "))
+            }
             Data::Error(text) => write!(f, "{text}"),
         }
     }
 }
 
-pub struct NodeViewer;
+pub struct NodeViewer {
+    /// Multiplier applied to the text size inside nodes
+    pub font_scale: f32,
+    pub groups: Vec<Group>,
+    /// Graph-space rects of the nodes, as of when they were last drawn
+    pub node_rects: HashMap<NodeId, Rect>,
+    /// Viewport of the last drawn frame
+    pub viewport: Option<Viewport>,
+    /// Where to put an Input node with the clipboard image, once the app gets to it
+    pub paste_image_at: Option<Pos2>,
+    /// Node to draw with an outline, like the current search result
+    pub highlighted: Option<NodeId>,
+}
+
+impl Default for NodeViewer {
+    fn default() -> Self {
+        Self {
+            font_scale: 1.0,
+            groups: Vec::new(),
+            node_rects: HashMap::new(),
+            viewport: None,
+            paste_image_at: None,
+            highlighted: None,
+        }
+    }
+}
 
 impl SnarlViewer<Node> for NodeViewer {
     fn title(&mut self, node: &Node) -> String {
@@ -70,23 +335,307 @@ impl SnarlViewer<Node> for NodeViewer {
                     Encoding::Base64 => "Base64 Encode",
                     Encoding::Base64UrlSafe => "Base64 URL Safe Encode",
                     Encoding::URL => "URL Encode",
+                    Encoding::Base58 => "Base58 Encode",
+                    Encoding::Punycode => "Punycode Encode",
                 },
                 Transformer::Decode { encoding } => match encoding {
                     Encoding::Base64 => "Base64 Decode",
                     Encoding::Base64UrlSafe => "Base64 URL Safe Decode",
                     Encoding::URL => "URL Decode",
+                    Encoding::Base58 => "Base58 Decode",
+                    Encoding::Punycode => "Punycode Decode",
                 },
                 Transformer::Uppercase => "Uppercase",
                 Transformer::Lowercase => "Lowercase",
+                Transformer::Checksum { algo } => match algo {
+                    ChecksumAlgo::Crc32 => "CRC32 Checksum",
+                    ChecksumAlgo::Adler32 => "Adler32 Checksum",
+                },
+                Transformer::FormDecode => "Form Decode",
+                Transformer::StripHtml { .. } => "Strip HTML",
+                Transformer::NumberFormat { .. } => "Number Format",
+                Transformer::MultiReplace { .. } => "Multi Replace",
+                Transformer::SortChars { .. } => "Sort Characters",
+                Transformer::UnixTime { .. } => "Unix Time",
+                Transformer::SwapEndian { .. } => "Swap Endianness",
+                Transformer::Bacon { decode } => {
+                    if *decode {
+                        "Bacon Decode"
+                    } else {
+                        "Bacon Encode"
+                    }
+                }
+                Transformer::ExtractEntities { kind } => match kind {
+                    EntityKind::Url => "Extract URLs",
+                    EntityKind::Email => "Extract Emails",
+                    EntityKind::Ipv4 => "Extract IPv4 Addresses",
+                },
+                Transformer::Rotations => "Rotations",
+                Transformer::Not => "Bitwise NOT",
+                Transformer::DetectCharset => "Detect Charset",
+                Transformer::Transcode { .. } => "Transcode",
+                Transformer::RegexEscape => "Regex Escape",
+                Transformer::Permutations { .. } => "Permutations",
+                Transformer::Math { .. } => "Math",
+                Transformer::Range { .. } => "Range",
+                Transformer::Caesar { .. } => "Caesar",
+                Transformer::Hmac { algo, .. } => match algo {
+                    HashAlgorithm::Sha1 => "HMAC-SHA1",
+                    HashAlgorithm::Sha256 => "HMAC-SHA256",
+                    HashAlgorithm::Sha512 => "HMAC-SHA512",
+                },
+                Transformer::ConfigConvert { .. } => "Config Convert",
+                Transformer::PartialUrlDecode { .. } => "Partial URL Decode",
+                Transformer::GuessXorKeyLen { .. } => "Guess XOR Key Length",
+                Transformer::SingleByteXor => "Single-byte XOR",
+                Transformer::CollapseWhitespace => "Collapse Whitespace",
+                Transformer::FixedWidth { .. } => "Fixed Width",
+                Transformer::Sequence { .. } => "Sequence",
+                Transformer::Base64Custom { decode, .. } => {
+                    if *decode {
+                        "Custom Base64 Decode"
+                    } else {
+                        "Custom Base64 Encode"
+                    }
+                }
+                Transformer::Luhn { verify } => {
+                    if *verify {
+                        "Luhn Verify"
+                    } else {
+                        "Luhn Check Digit"
+                    }
+                }
+                Transformer::DecodeBinaryFormat { fmt } => match fmt {
+                    BinFmt::MessagePack => "MessagePack Decode",
+                },
+                Transformer::GrayCode { to_gray } => {
+                    if *to_gray {
+                        "To Gray Code"
+                    } else {
+                        "From Gray Code"
+                    }
+                }
+                Transformer::RunLength { decode } => {
+                    if *decode {
+                        "Run-Length Decode"
+                    } else {
+                        "Run-Length Encode"
+                    }
+                }
+                Transformer::CommonPrefix { substring } => {
+                    if *substring {
+                        "Common Substring"
+                    } else {
+                        "Common Prefix"
+                    }
+                }
+                Transformer::IpFormat { .. } => "IP Format",
+                Transformer::Eval => "Eval",
+                Transformer::Translate { .. } => "Translate",
+                Transformer::Unescape { escape } => {
+                    if *escape {
+                        "Escape"
+                    } else {
+                        "Unescape"
+                    }
+                }
+                Transformer::LengthBreakdown => "Length Breakdown",
+                Transformer::SplitGraphemes => "Split Graphemes",
+                Transformer::BaseConvert { .. } => "Base Convert",
+                Transformer::Playfair { decrypt, .. } => {
+                    if *decrypt {
+                        "Playfair Decrypt"
+                    } else {
+                        "Playfair Encrypt"
+                    }
+                }
+                Transformer::A1Z26 { decode } => {
+                    if *decode {
+                        "A1Z26 Decode"
+                    } else {
+                        "A1Z26 Encode"
+                    }
+                }
+                Transformer::Between { .. } => "Between",
+                Transformer::TapCode { decode } => {
+                    if *decode {
+                        "Tap Code Decode"
+                    } else {
+                        "Tap Code Encode"
+                    }
+                }
+                Transformer::ModExp { .. } => "Modular Exponentiation",
+                Transformer::Factorize => "Factorize",
+                Transformer::GcdLcm { lcm } => {
+                    if *lcm {
+                        "LCM"
+                    } else {
+                        "GCD"
+                    }
+                }
+                Transformer::RegionalIndicators { decode } => {
+                    if *decode {
+                        "Regional Indicators Decode"
+                    } else {
+                        "Regional Indicators Encode"
+                    }
+                }
+                Transformer::Printf { .. } => "Printf",
+                Transformer::SwapNibbles => "Swap Nibbles",
+                Transformer::ColumnarTransposition { decrypt, .. } => {
+                    if *decrypt {
+                        "Columnar Transposition Decrypt"
+                    } else {
+                        "Columnar Transposition Encrypt"
+                    }
+                }
+                Transformer::Leet { decode } => {
+                    if *decode {
+                        "Leet Decode"
+                    } else {
+                        "Leet Encode"
+                    }
+                }
+                Transformer::Duration { .. } => "Duration",
+                Transformer::ParseKeyValue { .. } => "Parse Key/Value",
+                Transformer::MockCase { .. } => "Mock Case",
+                Transformer::Line { .. } => "Line",
+                Transformer::SortLines { .. } => "Sort Lines",
+                Transformer::UniqueLines { .. } => "Unique Lines",
+                Transformer::Grep { .. } => "Grep",
+                Transformer::NaturalSort { .. } => "Natural Sort",
+                Transformer::Markdown { .. } => "Markdown",
+                Transformer::Substitution { decrypt, .. } => {
+                    if *decrypt {
+                        "Substitution Decrypt"
+                    } else {
+                        "Substitution Encrypt"
+                    }
+                }
+                Transformer::Bom { add } => {
+                    if *add {
+                        "Add BOM"
+                    } else {
+                        "Strip BOM"
+                    }
+                }
+                Transformer::Hash { algo } => match algo {
+                    HashAlgorithm::Sha1 => "SHA1",
+                    HashAlgorithm::Sha256 => "SHA256",
+                    HashAlgorithm::Sha512 => "SHA512",
+                },
+                Transformer::Acrostic { .. } => "Acrostic",
+                Transformer::ImageInfo => "Image Info",
+                Transformer::Strings { .. } => "Strings",
             },
+            Node::Combine { combiner, .. } => match combiner {
+                Combiner::Anagrams => "Anagrams",
+                Combiner::Interleave => "Interleave",
+                Combiner::VigenereKey => "Vigenère Key",
+                Combiner::Similarity => "Similarity",
+                Combiner::SetOperation { .. } => "Set Operation",
+            },
+            Node::Route { .. } => "Route",
+            Node::Collect { .. } => "Collect",
+            Node::Snapshot { .. } => "Snapshot",
         }
         .into()
     }
 
+    fn has_node_style(
+        &mut self,
+        _node: NodeId,
+        _inputs: &[egui_snarl::InPin],
+        _outputs: &[egui_snarl::OutPin],
+        _snarl: &Snarl<Node>,
+    ) -> bool {
+        self.font_scale != 1.0
+    }
+
+    fn apply_node_style(
+        &mut self,
+        style: &mut egui::Style,
+        _node: NodeId,
+        _inputs: &[egui_snarl::InPin],
+        _outputs: &[egui_snarl::OutPin],
+        _snarl: &Snarl<Node>,
+    ) {
+        for font in style.text_styles.values_mut() {
+            font.size *= self.font_scale;
+        }
+    }
+
+    fn node_frame(
+        &mut self,
+        default: egui::Frame,
+        node: NodeId,
+        _inputs: &[egui_snarl::InPin],
+        _outputs: &[egui_snarl::OutPin],
+        _snarl: &Snarl<Node>,
+    ) -> egui::Frame {
+        if self.highlighted == Some(node) {
+            default.stroke(Stroke::new(2.0, Color32::YELLOW))
+        } else {
+            default
+        }
+    }
+
+    fn final_node_rect(
+        &mut self,
+        node: NodeId,
+        _ui_rect: Rect,
+        graph_rect: Rect,
+        _ui: &mut Ui,
+        _scale: f32,
+        _snarl: &mut Snarl<Node>,
+    ) {
+        self.node_rects.insert(node, graph_rect);
+    }
+
+    fn draw_background(
+        &mut self,
+        background: Option<&BackgroundPattern>,
+        viewport: &Viewport,
+        snarl_style: &SnarlStyle,
+        style: &Style,
+        painter: &Painter,
+        _snarl: &Snarl<Node>,
+    ) {
+        if let Some(background) = background {
+            background.draw(viewport, snarl_style, style, painter);
+        }
+
+        for group in &self.groups {
+            if let Some(rect) = group.rect(&self.node_rects) {
+                let rect = Rect::from_min_max(
+                    viewport.graph_pos_to_screen(rect.min),
+                    viewport.graph_pos_to_screen(rect.max),
+                );
+                let corner_radius = 8.0 * viewport.scale;
+                painter.rect(
+                    rect,
+                    corner_radius,
+                    style.visuals.faint_bg_color.gamma_multiply(0.5),
+                    Stroke::new(1.0, style.visuals.weak_text_color()),
+                    StrokeKind::Inside,
+                );
+            }
+        }
+
+        self.viewport = Some(Viewport {
+            rect: viewport.rect,
+            scale: viewport.scale,
+            offset: viewport.offset,
+        });
+    }
+
     fn inputs(&mut self, node: &Node) -> usize {
         match node {
             Node::Input { .. } => 0,
             Node::Transform { .. } => 1,
+            Node::Combine { combiner, .. } => combiner.inputs(),
+            Node::Route { .. } | Node::Snapshot { .. } => 1,
+            Node::Collect { inputs, .. } => *inputs,
         }
     }
 
@@ -98,38 +647,70 @@ impl SnarlViewer<Node> for NodeViewer {
         _scale: f32,
         snarl: &mut Snarl<Node>,
     ) -> PinInfo {
-        match &*pin.remotes {
-            [] => match snarl[pin.id.node] {
+        match input_data(snarl, &pin.remotes) {
+            None => match &snarl[pin.id.node] {
                 Node::Input { .. } => unreachable!("Input takes no inputs"),
-                Node::Transform { .. } => {
+                Node::Transform { .. } | Node::Route { .. } | Node::Snapshot { .. } => {
                     ui.label("NO INPUT");
                     PinInfo::circle()
                 }
+                Node::Combine { combiner, .. } => {
+                    ui.label(format!(
+                        "{}: NO INPUT",
+                        combiner.input_names()[pin.id.input]
+                    ));
+                    PinInfo::circle()
+                }
+                Node::Collect { .. } => {
+                    ui.weak("connect to add");
+                    PinInfo::circle()
+                }
             },
-            [remote] => {
-                let input_data = snarl[remote.node].data();
+            Some(input_data) => {
+                snarl[pin.id.node].evaluate(&input_data);
                 match &mut snarl[pin.id.node] {
                     Node::Input { .. } => unreachable!("Out takes no inputs"),
-                    Node::Transform { data, transformer } => {
-                        *data = transformer.transform(&input_data);
+                    Node::Transform {
+                        data,
+                        transformer,
+                        bypassed,
+                        last_run,
+                        ..
+                    } => {
                         ui.label(format!("{data:?}"));
-                        match transformer {
-                            Transformer::Slice { from, to } => {
-                                ui.add(
-                                    egui::DragValue::new(from).range(0..=input_data.max_str_len()),
-                                );
-                                ui.add(
-                                    egui::DragValue::new(to)
-                                        .range(*from..=input_data.max_str_len()),
-                                );
-                            }
-                            _ => (),
+                        if *bypassed {
+                            ui.weak("bypassed");
+                        } else if let Some(run) = last_run {
+                            ui.weak(format_elapsed(run.elapsed));
+                        }
+                        if let Transformer::Slice { from, to } = transformer {
+                            ui.add(egui::DragValue::new(from).range(0..=input_data.max_str_len()));
+                            ui.add(
+                                egui::DragValue::new(to).range(*from..=input_data.max_str_len()),
+                            );
                         }
+                        let kind = transformer.accepts();
+                        if kind.accepts(&input_data) {
+                            color_pin(&input_data)
+                        } else {
+                            ui.colored_label(MISMATCH_COLOR, format!("expects {}", kind.label()));
+                            color_pin(&input_data).with_fill(MISMATCH_COLOR)
+                        }
+                    }
+                    Node::Combine { combiner, .. } => {
+                        ui.label(combiner.input_names()[pin.id.input]);
+                        color_pin(&input_data)
+                    }
+                    Node::Collect { .. } => {
+                        ui.label(format!("#{}", pin.id.input + 1));
+                        color_pin(&input_data)
+                    }
+                    Node::Route { data, .. } | Node::Snapshot { data, .. } => {
+                        ui.label(format!("{data:?}"));
                         color_pin(&input_data)
                     }
                 }
             }
-            _ => unreachable!("Too many inputs"),
         }
     }
 
@@ -137,6 +718,10 @@ impl SnarlViewer<Node> for NodeViewer {
         match node {
             Node::Input { .. } => 1,
             Node::Transform { .. } => 1,
+            Node::Combine { .. } => 1,
+            Node::Collect { .. } => 1,
+            Node::Route { .. } => 2,
+            Node::Snapshot { .. } => 1,
         }
     }
 
@@ -149,20 +734,42 @@ impl SnarlViewer<Node> for NodeViewer {
         snarl: &mut Snarl<Node>,
     ) -> PinInfo {
         match &mut snarl[pin.id.node] {
-            Node::Input { text } => {
+            Node::Input { text, .. } => {
                 ui.add(egui::TextEdit::multiline(text));
-                PinInfo::circle().with_fill(Color32::from_rgb(16, 255, 16))
+                if pin.remotes.is_empty() {
+                    ui.weak("⚠ not connected");
+                }
+                color_pin(&Data::Text(String::new()))
             }
-            Node::Transform { data, transformer } => {
+            Node::Transform {
+                data, transformer, ..
+            } => {
                 match transformer {
                     Transformer::Split { pattern } => {
                         ui.add(egui::TextEdit::singleline(pattern).hint_text("pattern"));
                     }
-                    Transformer::Join { separator } => {
+                    Transformer::Join {
+                        separator,
+                        prefix,
+                        suffix,
+                    } => {
                         ui.add(egui::TextEdit::singleline(separator).hint_text("separator"));
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(prefix)
+                                    .hint_text("prefix")
+                                    .desired_width(60.0),
+                            );
+                            ui.add(
+                                egui::TextEdit::singleline(suffix)
+                                    .hint_text("suffix")
+                                    .desired_width(60.0),
+                            );
+                        });
                     }
-                    Transformer::Find { pattern } => {
+                    Transformer::Find { pattern, flatten } => {
                         ui.add(egui::TextEdit::singleline(pattern).hint_text("pattern"));
+                        ui.checkbox(flatten, "flatten");
                     }
                     Transformer::Replace { pattern, replacer } => {
                         ui.add(egui::TextEdit::singleline(replacer).hint_text("replacer"));
@@ -172,11 +779,410 @@ impl SnarlViewer<Node> for NodeViewer {
                         ui.selectable_value(encoding, Encoding::Base64, "Base64");
                         ui.selectable_value(encoding, Encoding::Base64UrlSafe, "Base64 URL Safe");
                         ui.selectable_value(encoding, Encoding::URL, "URL");
+                        ui.selectable_value(encoding, Encoding::Base58, "Base58");
+                        ui.selectable_value(encoding, Encoding::Punycode, "Punycode");
+                    }
+                    Transformer::Checksum { algo } => {
+                        ui.selectable_value(algo, ChecksumAlgo::Crc32, "CRC32");
+                        ui.selectable_value(algo, ChecksumAlgo::Adler32, "Adler32");
+                    }
+                    Transformer::StripHtml { decode_entities } => {
+                        ui.checkbox(decode_entities, "decode entities");
+                    }
+                    Transformer::NumberFormat {
+                        thousands,
+                        decimals,
+                    } => {
+                        ui.checkbox(thousands, "thousands separators");
+                        let mut fixed = decimals.is_some();
+                        if ui.checkbox(&mut fixed, "fixed decimals").changed() {
+                            *decimals = fixed.then_some(2);
+                        }
+                        if let Some(decimals) = decimals {
+                            ui.add(egui::DragValue::new(decimals).range(0..=20));
+                        }
+                    }
+                    Transformer::MultiReplace { rules } => {
+                        let mut removed = None;
+                        for (i, (pattern, replacer)) in rules.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::TextEdit::singleline(pattern)
+                                        .hint_text("pattern")
+                                        .desired_width(80.0),
+                                );
+                                ui.add(
+                                    egui::TextEdit::singleline(replacer)
+                                        .hint_text("replacer")
+                                        .desired_width(80.0),
+                                );
+                                if ui.small_button("✖").clicked() {
+                                    removed = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = removed {
+                            rules.remove(i);
+                        }
+                        if ui.button("Add rule").clicked() {
+                            rules.push(Default::default());
+                        }
+                    }
+                    Transformer::SortChars { descending } => {
+                        ui.checkbox(descending, "descending");
+                    }
+                    Transformer::UnixTime { to_date } => {
+                        ui.selectable_value(to_date, true, "Timestamp to date");
+                        ui.selectable_value(to_date, false, "Date to timestamp");
+                    }
+                    Transformer::SwapEndian { word_size } => {
+                        ui.add(
+                            egui::DragValue::new(word_size)
+                                .range(1..=64)
+                                .suffix(" bytes"),
+                        );
+                    }
+                    Transformer::Bacon { decode } => {
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::ExtractEntities { kind } => {
+                        ui.selectable_value(kind, EntityKind::Url, "URLs");
+                        ui.selectable_value(kind, EntityKind::Email, "Emails");
+                        ui.selectable_value(kind, EntityKind::Ipv4, "IPv4 addresses");
+                    }
+                    Transformer::Transcode { from } => {
+                        egui::ComboBox::from_id_salt(pin.id.node)
+                            .selected_text(from.label())
+                            .show_ui(ui, |ui| {
+                                for charset in CharsetName::ALL {
+                                    ui.selectable_value(from, charset.clone(), charset.label());
+                                }
+                            });
+                    }
+                    Transformer::Permutations { k } => {
+                        let mut partial = k.is_some();
+                        if ui.checkbox(&mut partial, "partial").changed() {
+                            *k = partial.then_some(2);
+                        }
+                        if let Some(k) = k {
+                            ui.add(egui::DragValue::new(k).range(0..=16).prefix("k: "));
+                        }
+                    }
+                    Transformer::Math { func } => {
+                        egui::ComboBox::from_id_salt(pin.id.node)
+                            .selected_text(func.label())
+                            .show_ui(ui, |ui| {
+                                for f in MathFunc::ALL {
+                                    ui.selectable_value(func, f.clone(), f.label());
+                                }
+                            });
+                    }
+                    Transformer::Range { start, end, step } => {
+                        ui.add(egui::DragValue::new(start).prefix("start: "));
+                        ui.add(egui::DragValue::new(end).prefix("end: "));
+                        ui.add(egui::DragValue::new(step).prefix("step: "));
+                    }
+                    Transformer::Caesar { shift, digits } => {
+                        ui.add(
+                            egui::DragValue::new(shift)
+                                .range(-25..=25)
+                                .prefix("shift: "),
+                        );
+                        ui.checkbox(digits, "rotate digits");
+                    }
+                    Transformer::Hmac { algo, key } => {
+                        ui.add(egui::TextEdit::singleline(key).hint_text("key"));
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(algo, HashAlgorithm::Sha1, "SHA1");
+                            ui.selectable_value(algo, HashAlgorithm::Sha256, "SHA256");
+                            ui.selectable_value(algo, HashAlgorithm::Sha512, "SHA512");
+                        });
+                    }
+                    Transformer::ConfigConvert { from, to } => {
+                        for (fmt, label) in [(from, "from"), (to, "to")] {
+                            ui.horizontal(|ui| {
+                                ui.label(label);
+                                for f in ConfigFmt::ALL {
+                                    ui.selectable_value(fmt, f.clone(), f.label());
+                                }
+                            });
+                        }
+                    }
+                    Transformer::PartialUrlDecode { escapes } => {
+                        ui.add(
+                            egui::TextEdit::singleline(escapes).hint_text("escapes, e.g. %20 %3D"),
+                        );
+                    }
+                    Transformer::GuessXorKeyLen { max } => {
+                        ui.add(egui::DragValue::new(max).range(1..=256).prefix("max: "));
+                    }
+                    Transformer::FixedWidth { widths } => {
+                        // Keep the raw text around, so half-typed lists like "2," survive
+                        let id = egui::Id::new("fixed_width").with(pin.id.node);
+                        let mut text = ui.data_mut(|d| {
+                            d.get_temp_mut_or_insert_with(id, || {
+                                widths
+                                    .iter()
+                                    .map(usize::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            })
+                            .clone()
+                        });
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut text).hint_text("widths, e.g. 2, 3, 2"),
+                        );
+                        if response.changed() {
+                            if let Ok(parsed) = text
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|w| !w.is_empty())
+                                .map(str::parse)
+                                .collect()
+                            {
+                                *widths = parsed;
+                            }
+                            ui.data_mut(|d| d.insert_temp(id, text));
+                        }
+                    }
+                    Transformer::Sequence { kind, count } => {
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(kind, SeqKind::Fibonacci, "Fibonacci");
+                            ui.selectable_value(kind, SeqKind::Primes, "Primes");
+                            ui.selectable_value(kind, SeqKind::Squares, "Squares");
+                        });
+                        ui.add(
+                            egui::DragValue::new(count)
+                                .range(0..=MAX_SEQUENCE_LEN)
+                                .prefix("count: "),
+                        );
+                    }
+                    Transformer::Base64Custom { alphabet, decode } => {
+                        ui.add(egui::TextEdit::singleline(alphabet).hint_text("alphabet"));
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::Luhn { verify } => {
+                        ui.checkbox(verify, "verify");
+                    }
+                    Transformer::DecodeBinaryFormat { fmt } => {
+                        ui.selectable_value(fmt, BinFmt::MessagePack, "MessagePack");
+                    }
+                    Transformer::GrayCode { to_gray } => {
+                        ui.selectable_value(to_gray, true, "binary → Gray");
+                        ui.selectable_value(to_gray, false, "Gray → binary");
+                    }
+                    Transformer::RunLength { decode } => {
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::CommonPrefix { substring } => {
+                        ui.checkbox(substring, "anywhere, not just at the start");
+                    }
+                    Transformer::IpFormat { to } => {
+                        ui.horizontal(|ui| {
+                            ui.label("to");
+                            ui.selectable_value(to, IpFormat::Dotted, "dotted");
+                            ui.selectable_value(to, IpFormat::Integer, "integer");
+                            ui.selectable_value(to, IpFormat::Hex, "hex");
+                        });
+                    }
+                    Transformer::Translate { from, to } => {
+                        ui.add(egui::TextEdit::singleline(from).hint_text("from"));
+                        ui.add(egui::TextEdit::singleline(to).hint_text("to"));
+                    }
+                    Transformer::Unescape { escape } => {
+                        ui.checkbox(escape, "escape instead");
+                    }
+                    Transformer::BaseConvert { from, to } => {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(from).range(2..=36).prefix("from: "));
+                            ui.add(egui::DragValue::new(to).range(2..=36).prefix("to: "));
+                        });
+                    }
+                    Transformer::Playfair { key, decrypt } => {
+                        ui.add(egui::TextEdit::singleline(key).hint_text("key"));
+                        ui.checkbox(decrypt, "decrypt");
+                    }
+                    Transformer::A1Z26 { decode } => {
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::Between { start, end, all } => {
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(start)
+                                    .hint_text("start")
+                                    .desired_width(60.0),
+                            );
+                            ui.add(
+                                egui::TextEdit::singleline(end)
+                                    .hint_text("end")
+                                    .desired_width(60.0),
+                            );
+                        });
+                        ui.checkbox(all, "all");
+                    }
+                    Transformer::TapCode { decode } => {
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::ModExp { base, exp, modulus } => {
+                        for (field, hint) in
+                            [(base, "base"), (exp, "exponent"), (modulus, "modulus")]
+                        {
+                            ui.add(
+                                egui::TextEdit::singleline(field)
+                                    .hint_text(format!("{hint} (input if empty)")),
+                            );
+                        }
+                    }
+                    Transformer::GcdLcm { lcm } => {
+                        ui.checkbox(lcm, "LCM");
+                    }
+                    Transformer::RegionalIndicators { decode } => {
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::Printf { spec } => {
+                        ui.add(egui::TextEdit::singleline(spec).hint_text("spec, e.g. %05.2f"));
+                    }
+                    Transformer::ColumnarTransposition { key, decrypt } => {
+                        ui.add(egui::TextEdit::singleline(key).hint_text("key"));
+                        ui.checkbox(decrypt, "decrypt");
+                    }
+                    Transformer::Leet { decode } => {
+                        ui.checkbox(decode, "decode");
+                    }
+                    Transformer::Duration { to } => {
+                        ui.horizontal(|ui| {
+                            ui.label("to");
+                            ui.selectable_value(to, DurationFmt::Seconds, "seconds");
+                            ui.selectable_value(to, DurationFmt::Human, "1h2m3s");
+                        });
+                    }
+                    Transformer::ParseKeyValue { line_sep, kv_sep } => {
+                        ui.add(
+                            egui::TextEdit::singleline(line_sep)
+                                .hint_text("entry separator (lines)"),
+                        );
+                        ui.add(egui::TextEdit::singleline(kv_sep).hint_text("key/value separator"));
+                    }
+                    Transformer::MockCase { seed } => {
+                        ui.horizontal(|ui| {
+                            ui.label("seed");
+                            ui.add(egui::DragValue::new(seed));
+                            if ui.button("🎲").on_hover_text("Reroll").clicked() {
+                                *seed = SystemTime::now()
+                                    .duration_since(UNIX_EPOCH)
+                                    .map_or(seed.wrapping_add(1), |d| d.as_nanos() as u64);
+                            }
+                        });
+                    }
+                    Transformer::Line { index, from_end } => {
+                        ui.add(
+                            egui::DragValue::new(index)
+                                .range(1..=usize::MAX)
+                                .prefix("line "),
+                        );
+                        ui.checkbox(from_end, "from the end");
+                    }
+                    Transformer::SortLines {
+                        descending,
+                        numeric,
+                    } => {
+                        ui.checkbox(descending, "descending");
+                        ui.checkbox(numeric, "numeric");
+                    }
+                    Transformer::UniqueLines { adjacent_only } => {
+                        ui.checkbox(adjacent_only, "adjacent only");
+                    }
+                    Transformer::Grep { pattern, invert } => {
+                        ui.add(egui::TextEdit::singleline(pattern).hint_text("pattern"));
+                        ui.checkbox(invert, "invert");
+                    }
+                    Transformer::NaturalSort { descending } => {
+                        ui.checkbox(descending, "descending");
+                    }
+                    Transformer::Markdown { kind } => {
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(kind, MdKind::Bullets, "bullets");
+                            ui.selectable_value(kind, MdKind::Numbered, "numbered");
+                            ui.selectable_value(kind, MdKind::Table, "table");
+                        });
+                    }
+                    Transformer::Substitution {
+                        cipher_alphabet,
+                        decrypt,
+                    } => {
+                        ui.add(
+                            egui::TextEdit::singleline(cipher_alphabet)
+                                .hint_text("cipher alphabet"),
+                        );
+                        ui.checkbox(decrypt, "decrypt");
+                    }
+                    Transformer::Bom { add } => {
+                        ui.checkbox(add, "add");
+                    }
+                    Transformer::Hash { algo } => {
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(algo, HashAlgorithm::Sha1, "SHA1");
+                            ui.selectable_value(algo, HashAlgorithm::Sha256, "SHA256");
+                            ui.selectable_value(algo, HashAlgorithm::Sha512, "SHA512");
+                        });
+                    }
+                    Transformer::Acrostic { position } => {
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(position, AcrosticPos::First, "first");
+                            ui.selectable_value(position, AcrosticPos::Last, "last");
+                            if ui
+                                .selectable_label(matches!(position, AcrosticPos::Nth(_)), "nth")
+                                .clicked()
+                            {
+                                *position = AcrosticPos::Nth(2);
+                            }
+                        });
+                        if let AcrosticPos::Nth(n) = position {
+                            ui.add(
+                                egui::DragValue::new(n)
+                                    .range(1..=usize::MAX)
+                                    .prefix("character "),
+                            );
+                        }
+                    }
+                    Transformer::Strings { min_len } => {
+                        ui.add(
+                            egui::DragValue::new(min_len)
+                                .range(1..=256)
+                                .prefix("min length "),
+                        );
                     }
                     _ => (),
                 }
                 color_pin(data)
             }
+            Node::Combine { .. } => show_combine(pin.id.node, ui, snarl),
+            Node::Collect { .. } => {
+                collect(snarl, pin.id.node);
+                let data = snarl[pin.id.node].data();
+                ui.label(format!("{data:?}"));
+                color_pin(&data)
+            }
+            Node::Route { pattern, .. } => {
+                if pin.id.output == 0 {
+                    ui.add(egui::TextEdit::singleline(pattern).hint_text("pattern"));
+                    ui.label("match");
+                } else {
+                    ui.label("no match");
+                }
+                color_pin(&snarl[pin.id.node].output(pin.id.output))
+            }
+            Node::Snapshot { captured, .. } => match captured {
+                Some(captured) => {
+                    ui.label(format!("{captured:?}"));
+                    ui.weak("captured");
+                    color_pin(captured)
+                }
+                None => {
+                    ui.weak("live");
+                    color_pin(&snarl[pin.id.node].data())
+                }
+            },
         }
     }
 
@@ -193,115 +1199,107 @@ impl SnarlViewer<Node> for NodeViewer {
     ) {
         ui.label("Add node");
         if ui.button("Input").clicked() {
-            snarl.insert_node(
-                pos,
-                Node::Input {
-                    text: String::new(),
-                },
-            );
+            snarl.insert_node(pos, Node::input(String::new()));
             ui.close_menu();
         }
-        if ui.button("Split").clicked() {
-            snarl.insert_node(
-                pos,
-                Node::Transform {
-                    data: Data::List(Vec::new()),
-                    transformer: Transformer::Split {
-                        pattern: String::new(),
-                    },
-                },
-            );
+        if ui.button("Input from clipboard image").clicked() {
+            self.paste_image_at = Some(pos);
             ui.close_menu();
         }
-        if ui.button("Join").clicked() {
-            snarl.insert_node(
-                pos,
-                Node::Transform {
-                    data: Data::List(Vec::new()),
-                    transformer: Transformer::Join {
-                        separator: String::new(),
-                    },
-                },
-            );
+        if let Some(node) = transform_menu(ui) {
+            snarl.insert_node(pos, node);
             ui.close_menu();
         }
-        if ui.button("Find").clicked() {
+        if ui.button("Anagrams").clicked() {
             snarl.insert_node(
                 pos,
-                Node::Transform {
-                    data: Data::List(Vec::new()),
-                    transformer: Transformer::Find {
-                        pattern: String::new(),
-                    },
-                },
+                Node::combine(Combiner::Anagrams, Data::List(Vec::new())),
             );
             ui.close_menu();
         }
-        if ui.button("Replace").clicked() {
+        if ui.button("Interleave").clicked() {
             snarl.insert_node(
                 pos,
-                Node::Transform {
-                    data: Data::List(Vec::new()),
-                    transformer: Transformer::Replace {
-                        pattern: String::new(),
-                        replacer: String::new(),
-                    },
-                },
+                Node::combine(Combiner::Interleave, Data::List(Vec::new())),
             );
             ui.close_menu();
         }
-        if ui.button("Slice").clicked() {
+        if ui.button("Vigenère Key").clicked() {
             snarl.insert_node(
                 pos,
-                Node::Transform {
-                    data: Data::Text(String::new()),
-                    transformer: Transformer::Slice { from: 0, to: 0 },
-                },
+                Node::combine(Combiner::VigenereKey, Data::Text(String::new())),
             );
             ui.close_menu();
         }
-        if ui.button("Encode").clicked() {
+        if ui.button("Similarity").clicked() {
             snarl.insert_node(
                 pos,
-                Node::Transform {
-                    data: Data::Text(String::new()),
-                    transformer: Transformer::Encode {
-                        encoding: Encoding::Base64,
-                    },
-                },
+                Node::combine(Combiner::Similarity, Data::Text(String::new())),
             );
             ui.close_menu();
         }
-        if ui.button("Decode").clicked() {
+        if ui.button("Set Operation").clicked() {
             snarl.insert_node(
                 pos,
-                Node::Transform {
-                    data: Data::Text(String::new()),
-                    transformer: Transformer::Decode {
-                        encoding: Encoding::Base64,
+                Node::combine(
+                    Combiner::SetOperation {
+                        op: SetOp::Intersection,
                     },
-                },
+                    Data::List(Vec::new()),
+                ),
             );
             ui.close_menu();
         }
-        if ui.button("Uppercase").clicked() {
-            snarl.insert_node(
-                pos,
-                Node::Transform {
-                    data: Data::Text(String::new()),
-                    transformer: Transformer::Uppercase,
-                },
-            );
+        if ui.button("Route").clicked() {
+            snarl.insert_node(pos, Node::route(String::new()));
             ui.close_menu();
         }
-        if ui.button("Lowercase").clicked() {
-            snarl.insert_node(
-                pos,
-                Node::Transform {
-                    data: Data::Text(String::new()),
-                    transformer: Transformer::Lowercase,
-                },
-            );
+        if ui.button("Collect").clicked() {
+            snarl.insert_node(pos, Node::collect());
+            ui.close_menu();
+        }
+        if ui.button("Snapshot").clicked() {
+            snarl.insert_node(pos, Node::snapshot());
+            ui.close_menu();
+        }
+    }
+
+    fn has_dropped_wire_menu(&mut self, _src_pins: AnyPins, _snarl: &mut Snarl<Node>) -> bool {
+        true
+    }
+
+    fn connect(
+        &mut self,
+        from: &egui_snarl::OutPin,
+        to: &egui_snarl::InPin,
+        snarl: &mut Snarl<Node>,
+    ) {
+        connect(snarl, from.id, to.id);
+    }
+
+    fn show_dropped_wire_menu(
+        &mut self,
+        pos: egui::Pos2,
+        ui: &mut Ui,
+        _scale: f32,
+        src_pins: AnyPins,
+        snarl: &mut Snarl<Node>,
+    ) {
+        ui.label("Add transformer");
+        if let Some(node) = transform_menu(ui) {
+            let node = snarl.insert_node(pos, node);
+            match src_pins {
+                AnyPins::Out(outputs) => {
+                    for output in outputs {
+                        connect(snarl, *output, InPinId { node, input: 0 });
+                    }
+                }
+                AnyPins::In(inputs) => {
+                    for input in inputs {
+                        connect(snarl, OutPinId { node, output: 0 }, *input);
+                    }
+                }
+            }
             ui.close_menu();
         }
     }
@@ -313,13 +1311,60 @@ impl SnarlViewer<Node> for NodeViewer {
     fn show_node_menu(
         &mut self,
         node: egui_snarl::NodeId,
-        _inputs: &[egui_snarl::InPin],
-        _outputs: &[egui_snarl::OutPin],
+        inputs: &[egui_snarl::InPin],
+        outputs: &[egui_snarl::OutPin],
         ui: &mut Ui,
         _scale: f32,
         snarl: &mut Snarl<Node>,
     ) {
         ui.label("Node menu");
+        for pin in inputs {
+            for remote in &pin.remotes {
+                let title = self.title(&snarl[remote.node]);
+                if ui
+                    .button(format!("Delete connection from {title}"))
+                    .clicked()
+                {
+                    snarl.disconnect(*remote, pin.id);
+                    ui.close_menu();
+                }
+            }
+        }
+        for pin in outputs {
+            for remote in &pin.remotes {
+                let title = self.title(&snarl[remote.node]);
+                if ui.button(format!("Delete connection to {title}")).clicked() {
+                    snarl.disconnect(pin.id, *remote);
+                    ui.close_menu();
+                }
+            }
+        }
+        if let Node::Transform { bypassed, .. } = &mut snarl[node]
+            && ui.checkbox(bypassed, "Bypass").clicked()
+        {
+            ui.close_menu();
+        }
+        if let Node::Snapshot { captured, .. } = &snarl[node] {
+            let captured = captured.is_some();
+            if ui.button("Capture").clicked() {
+                snarl[node].capture();
+                ui.close_menu();
+            }
+            if captured && ui.button("Release").clicked() {
+                snarl[node].release();
+                ui.close_menu();
+            }
+        }
+        if snarl[node].locked().is_some() {
+            if ui.button("Unlock position").clicked() {
+                snarl[node].set_locked(None);
+                ui.close_menu();
+            }
+        } else if ui.button("Lock position").clicked() {
+            let pos = snarl.get_node_info(node).map(|info| info.pos);
+            snarl[node].set_locked(pos);
+            ui.close_menu();
+        }
         if ui.button("Remove").clicked() {
             snarl.remove_node(node);
             ui.close_menu();
@@ -327,11 +1372,920 @@ impl SnarlViewer<Node> for NodeViewer {
     }
 }
 
-fn color_pin(data: &Data) -> PinInfo {
-    let color = match data {
+/// Buttons for every transformer, returning the node for the one that was clicked
+fn transform_menu(ui: &mut Ui) -> Option<Node> {
+    if ui.button("Split").clicked() {
+        return Some(Node::transform(
+            Transformer::Split {
+                pattern: String::new(),
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Join").clicked() {
+        return Some(Node::transform(
+            Transformer::Join {
+                separator: String::new(),
+                prefix: String::new(),
+                suffix: String::new(),
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Find").clicked() {
+        return Some(Node::transform(
+            Transformer::Find {
+                pattern: String::new(),
+                flatten: false,
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Replace").clicked() {
+        return Some(Node::transform(
+            Transformer::Replace {
+                pattern: String::new(),
+                replacer: String::new(),
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Slice").clicked() {
+        return Some(Node::transform(
+            Transformer::Slice { from: 0, to: 0 },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Encode").clicked() {
+        return Some(Node::transform(
+            Transformer::Encode {
+                encoding: Encoding::Base64,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Decode").clicked() {
+        return Some(Node::transform(
+            Transformer::Decode {
+                encoding: Encoding::Base64,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Uppercase").clicked() {
+        return Some(Node::transform(
+            Transformer::Uppercase,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Lowercase").clicked() {
+        return Some(Node::transform(
+            Transformer::Lowercase,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Checksum").clicked() {
+        return Some(Node::transform(
+            Transformer::Checksum {
+                algo: ChecksumAlgo::Crc32,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Form Decode").clicked() {
+        return Some(Node::transform(
+            Transformer::FormDecode,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Strip HTML").clicked() {
+        return Some(Node::transform(
+            Transformer::StripHtml {
+                decode_entities: true,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Number Format").clicked() {
+        return Some(Node::transform(
+            Transformer::NumberFormat {
+                thousands: true,
+                decimals: None,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Multi Replace").clicked() {
+        return Some(Node::transform(
+            Transformer::MultiReplace {
+                rules: vec![Default::default()],
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Sort Characters").clicked() {
+        return Some(Node::transform(
+            Transformer::SortChars { descending: false },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Unix Time").clicked() {
+        return Some(Node::transform(
+            Transformer::UnixTime { to_date: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Swap Endianness").clicked() {
+        return Some(Node::transform(
+            Transformer::SwapEndian { word_size: 4 },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Bacon").clicked() {
+        return Some(Node::transform(
+            Transformer::Bacon { decode: false },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Extract").clicked() {
+        return Some(Node::transform(
+            Transformer::ExtractEntities {
+                kind: EntityKind::Url,
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Rotations").clicked() {
+        return Some(Node::transform(
+            Transformer::Rotations,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Bitwise NOT").clicked() {
+        return Some(Node::transform(Transformer::Not, Data::Bytes(Vec::new())));
+    }
+    if ui.button("Detect Charset").clicked() {
+        return Some(Node::transform(
+            Transformer::DetectCharset,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Transcode").clicked() {
+        return Some(Node::transform(
+            Transformer::Transcode {
+                from: CharsetName::Windows1252,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Regex Escape").clicked() {
+        return Some(Node::transform(
+            Transformer::RegexEscape,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Permutations").clicked() {
+        return Some(Node::transform(
+            Transformer::Permutations { k: None },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Math").clicked() {
+        return Some(Node::transform(
+            Transformer::Math {
+                func: MathFunc::Sqrt,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Range").clicked() {
+        return Some(Node::transform(
+            Transformer::Range {
+                start: 1,
+                end: 10,
+                step: 1,
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Caesar").clicked() {
+        return Some(Node::transform(
+            Transformer::Caesar {
+                shift: 13,
+                digits: false,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("HMAC").clicked() {
+        return Some(Node::transform(
+            Transformer::Hmac {
+                algo: HashAlgorithm::Sha256,
+                key: String::new(),
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Config Convert").clicked() {
+        return Some(Node::transform(
+            Transformer::ConfigConvert {
+                from: ConfigFmt::Json,
+                to: ConfigFmt::Yaml,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Partial URL Decode").clicked() {
+        return Some(Node::transform(
+            Transformer::PartialUrlDecode {
+                escapes: "%20".into(),
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Guess XOR Key Length").clicked() {
+        return Some(Node::transform(
+            Transformer::GuessXorKeyLen { max: 40 },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Single-byte XOR").clicked() {
+        return Some(Node::transform(
+            Transformer::SingleByteXor,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Collapse Whitespace").clicked() {
+        return Some(Node::transform(
+            Transformer::CollapseWhitespace,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Fixed Width").clicked() {
+        return Some(Node::transform(
+            Transformer::FixedWidth { widths: Vec::new() },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Sequence").clicked() {
+        return Some(Node::transform(
+            Transformer::Sequence {
+                kind: SeqKind::Fibonacci,
+                count: 10,
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Custom Base64").clicked() {
+        return Some(Node::transform(
+            Transformer::Base64Custom {
+                alphabet: "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/="
+                    .into(),
+                decode: true,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Luhn").clicked() {
+        return Some(Node::transform(
+            Transformer::Luhn { verify: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("MessagePack Decode").clicked() {
+        return Some(Node::transform(
+            Transformer::DecodeBinaryFormat {
+                fmt: BinFmt::MessagePack,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Gray Code").clicked() {
+        return Some(Node::transform(
+            Transformer::GrayCode { to_gray: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Run-Length").clicked() {
+        return Some(Node::transform(
+            Transformer::RunLength { decode: false },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Common Prefix").clicked() {
+        return Some(Node::transform(
+            Transformer::CommonPrefix { substring: false },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("IP Format").clicked() {
+        return Some(Node::transform(
+            Transformer::IpFormat {
+                to: IpFormat::Integer,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Eval").clicked() {
+        return Some(Node::transform(
+            Transformer::Eval,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Translate").clicked() {
+        return Some(Node::transform(
+            Transformer::Translate {
+                from: String::new(),
+                to: String::new(),
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Unescape").clicked() {
+        return Some(Node::transform(
+            Transformer::Unescape { escape: false },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Length Breakdown").clicked() {
+        return Some(Node::transform(
+            Transformer::LengthBreakdown,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Split Graphemes").clicked() {
+        return Some(Node::transform(
+            Transformer::SplitGraphemes,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Base Convert").clicked() {
+        return Some(Node::transform(
+            Transformer::BaseConvert { from: 16, to: 10 },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Playfair").clicked() {
+        return Some(Node::transform(
+            Transformer::Playfair {
+                key: String::new(),
+                decrypt: true,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("A1Z26").clicked() {
+        return Some(Node::transform(
+            Transformer::A1Z26 { decode: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Between").clicked() {
+        return Some(Node::transform(
+            Transformer::Between {
+                start: "[".into(),
+                end: "]".into(),
+                all: true,
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Tap Code").clicked() {
+        return Some(Node::transform(
+            Transformer::TapCode { decode: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Modular Exponentiation").clicked() {
+        return Some(Node::transform(
+            Transformer::ModExp {
+                base: String::new(),
+                exp: "65537".into(),
+                modulus: String::new(),
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Factorize").clicked() {
+        return Some(Node::transform(
+            Transformer::Factorize,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("GCD / LCM").clicked() {
+        return Some(Node::transform(
+            Transformer::GcdLcm { lcm: false },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Regional Indicators").clicked() {
+        return Some(Node::transform(
+            Transformer::RegionalIndicators { decode: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Printf").clicked() {
+        return Some(Node::transform(
+            Transformer::Printf { spec: "%d".into() },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Swap Nibbles").clicked() {
+        return Some(Node::transform(
+            Transformer::SwapNibbles,
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Columnar Transposition").clicked() {
+        return Some(Node::transform(
+            Transformer::ColumnarTransposition {
+                key: String::new(),
+                decrypt: true,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Leet").clicked() {
+        return Some(Node::transform(
+            Transformer::Leet { decode: true },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Duration").clicked() {
+        return Some(Node::transform(
+            Transformer::Duration {
+                to: DurationFmt::Human,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Parse Key/Value").clicked() {
+        return Some(Node::transform(
+            Transformer::ParseKeyValue {
+                line_sep: String::new(),
+                kv_sep: "=".into(),
+            },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Mock Case").clicked() {
+        return Some(Node::transform(
+            Transformer::MockCase { seed: 0 },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Line").clicked() {
+        return Some(Node::transform(
+            Transformer::Line {
+                index: 1,
+                from_end: false,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Sort Lines").clicked() {
+        return Some(Node::transform(
+            Transformer::SortLines {
+                descending: false,
+                numeric: false,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Unique Lines").clicked() {
+        return Some(Node::transform(
+            Transformer::UniqueLines {
+                adjacent_only: false,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Grep").clicked() {
+        return Some(Node::transform(
+            Transformer::Grep {
+                pattern: String::new(),
+                invert: false,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Natural Sort").clicked() {
+        return Some(Node::transform(
+            Transformer::NaturalSort { descending: false },
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Markdown").clicked() {
+        return Some(Node::transform(
+            Transformer::Markdown {
+                kind: MdKind::Bullets,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Substitution").clicked() {
+        return Some(Node::transform(
+            Transformer::Substitution {
+                cipher_alphabet: "abcdefghijklmnopqrstuvwxyz".into(),
+                decrypt: true,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("BOM").clicked() {
+        return Some(Node::transform(
+            Transformer::Bom { add: false },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Hash").clicked() {
+        return Some(Node::transform(
+            Transformer::Hash {
+                algo: HashAlgorithm::Sha256,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Acrostic").clicked() {
+        return Some(Node::transform(
+            Transformer::Acrostic {
+                position: AcrosticPos::First,
+            },
+            Data::Text(String::new()),
+        ));
+    }
+    if ui.button("Image Info").clicked() {
+        return Some(Node::transform(
+            Transformer::ImageInfo,
+            Data::List(Vec::new()),
+        ));
+    }
+    if ui.button("Strings").clicked() {
+        return Some(Node::transform(
+            Transformer::Strings { min_len: 4 },
+            Data::List(Vec::new()),
+        ));
+    }
+    None
+}
+
+/// Connects two pins. An output can feed any number of inputs, but an input takes a single
+/// wire, so whatever was connected to it before is disconnected
+pub fn connect(snarl: &mut Snarl<Node>, from: OutPinId, to: InPinId) {
+    snarl.drop_inputs(to);
+    snarl.connect(from, to);
+}
+
+/// Data arriving at an input pin, if it's connected. Inputs take a single wire, but a loaded graph
+/// may still have more, which is an error rather than an arbitrary pick
+fn input_data(snarl: &Snarl<Node>, remotes: &[OutPinId]) -> Option<Data> {
+    match remotes {
+        [] => None,
+        [remote] => Some(snarl[remote.node].output(remote.output)),
+        _ => Some(Data::Error("multiple inputs not supported".into())),
+    }
+}
+
+/// Evaluates a collect node from its connected inputs, in pin order, and resizes it to keep one
+/// spare input after the last connected one
+fn collect(snarl: &mut Snarl<Node>, node: NodeId) {
+    let wired = snarl
+        .wires()
+        .filter(|(_, to)| to.node == node)
+        .map(|(_, to)| to.input + 1)
+        .max()
+        .unwrap_or(0);
+    let collected = (0..wired)
+        .filter_map(|input| input_data(snarl, &snarl.in_pin(InPinId { node, input }).remotes))
+        .collect();
+    let Node::Collect { data, inputs, .. } = &mut snarl[node] else {
+        unreachable!("Not a collect node")
+    };
+    *data = Data::List(collected);
+    *inputs = wired + 1;
+}
+
+/// Combiners need every input at once, so they're evaluated when showing the output
+fn show_combine(node: NodeId, ui: &mut Ui, snarl: &mut Snarl<Node>) -> PinInfo {
+    let Node::Combine { combiner, .. } = &snarl[node] else {
+        unreachable!("Not a combine node")
+    };
+    let inputs = (0..combiner.inputs())
+        .map(|input| input_data(snarl, &snarl.in_pin(InPinId { node, input }).remotes))
+        .collect::<Option<Vec<_>>>();
+
+    let Node::Combine {
+        combiner,
+        data,
+        last_run,
+        ..
+    } = &mut snarl[node]
+    else {
+        unreachable!("Not a combine node")
+    };
+    if let Combiner::SetOperation { op } = combiner {
+        ui.horizontal(|ui| {
+            ui.selectable_value(op, SetOp::Union, "union");
+            ui.selectable_value(op, SetOp::Intersection, "intersection");
+            ui.selectable_value(op, SetOp::Difference, "difference");
+        });
+    }
+    LastRun::rerun(last_run, &inputs, combiner, data, || match &inputs {
+        Some(inputs) => combiner.combine(inputs),
+        None => Data::Error("Missing input".into()),
+    });
+    ui.label(format!("{data:?}"));
+    if let Some(run) = last_run {
+        ui.weak(format_elapsed(run.elapsed));
+    }
+    color_pin(data)
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{:.1}ms", elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Whether the pattern matches a text, or any element of a list
+fn route_matches(re: &Regex, data: &Data) -> Option<bool> {
+    match data {
+        Data::Text(text) => Some(re.is_match(text)),
+        Data::Bytes(bytes) => Some(re.is_match(&String::from_utf8_lossy(bytes))),
+        Data::List(list) => {
+            let mut matched = false;
+            for d in list {
+                matched |= route_matches(re, d)?;
+            }
+            Some(matched)
+        }
+        Data::Error(_) => None,
+    }
+}
+
+/// Pin color for input a transformer isn't meant for
+const MISMATCH_COLOR: Color32 = Color32::from_rgb(255, 16, 255);
+
+pub fn data_color(data: &Data) -> Color32 {
+    match data {
         Data::Text(_) => Color32::from_rgb(16, 255, 16),
         Data::List(_) => Color32::from_rgb(16, 16, 255),
+        Data::Bytes(_) => Color32::from_rgb(255, 192, 16),
         Data::Error(_) => Color32::from_rgb(255, 16, 16),
-    };
-    PinInfo::circle().with_fill(color)
+    }
+}
+
+/// Colors both the pin and its wires by the type of data
+fn color_pin(data: &Data) -> PinInfo {
+    let color = data_color(data);
+    PinInfo::circle().with_fill(color).with_wire_color(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_bypass() {
+        let input = Data::Text("Sample Text".into());
+        let mut node = Node::transform(Transformer::Uppercase, Data::Text(String::new()));
+
+        node.evaluate(&input);
+        assert_eq!(node.data(), Data::Text("SAMPLE TEXT".into()));
+
+        if let Node::Transform { bypassed, .. } = &mut node {
+            *bypassed = true;
+        }
+        node.evaluate(&input);
+        assert_eq!(node.data(), input);
+
+        if let Node::Transform { bypassed, .. } = &mut node {
+            *bypassed = false;
+        }
+        node.evaluate(&input);
+        assert_eq!(node.data(), Data::Text("SAMPLE TEXT".into()));
+    }
+
+    #[test]
+    fn test_last_run() {
+        let input = Data::Text("Sample Text".into());
+        let mut node = Node::transform(Transformer::Uppercase, Data::Text(String::new()));
+        node.evaluate(&input);
+
+        // Nothing changed, so the data isn't recomputed
+        let Node::Transform { data, .. } = &mut node else {
+            unreachable!()
+        };
+        *data = Data::Text("stale".into());
+        node.evaluate(&input);
+        assert_eq!(node.data(), Data::Text("stale".into()));
+
+        node.evaluate(&Data::Text("Other".into()));
+        assert_eq!(node.data(), Data::Text("OTHER".into()));
+
+        let Node::Transform { transformer, .. } = &mut node else {
+            unreachable!()
+        };
+        *transformer = Transformer::Lowercase;
+        node.evaluate(&Data::Text("Other".into()));
+        assert_eq!(node.data(), Data::Text("other".into()));
+    }
+
+    #[test]
+    fn test_route() {
+        let mut node = Node::route("^[0-9]+$".into());
+
+        node.evaluate(&Data::Text("42".into()));
+        assert_eq!(node.output(0), Data::Text("42".into()));
+        assert_eq!(node.output(1), Data::Text(String::new()));
+
+        node.evaluate(&Data::Text("forty-two".into()));
+        assert_eq!(node.output(0), Data::Text(String::new()));
+        assert_eq!(node.output(1), Data::Text("forty-two".into()));
+
+        node.evaluate(&Data::Error("Invalid pattern".into()));
+        assert_eq!(node.output(0), Data::Error("Input error".into()));
+    }
+
+    #[test]
+    fn test_connect() {
+        let mut snarl = Snarl::new();
+        let first = snarl.insert_node(Pos2::ZERO, Node::input("first".into()));
+        let second = snarl.insert_node(Pos2::ZERO, Node::input("second".into()));
+        let upper = snarl.insert_node(
+            Pos2::ZERO,
+            Node::transform(Transformer::Uppercase, Data::Text(String::new())),
+        );
+        let lower = snarl.insert_node(
+            Pos2::ZERO,
+            Node::transform(Transformer::Lowercase, Data::Text(String::new())),
+        );
+        let first_out = OutPinId {
+            node: first,
+            output: 0,
+        };
+        let upper_in = InPinId {
+            node: upper,
+            input: 0,
+        };
+
+        // Fan-out: one output feeds several inputs
+        connect(&mut snarl, first_out, upper_in);
+        connect(
+            &mut snarl,
+            first_out,
+            InPinId {
+                node: lower,
+                input: 0,
+            },
+        );
+        assert_eq!(snarl.out_pin(first_out).remotes.len(), 2);
+
+        // Fan-in: a new wire replaces the old one
+        let second_out = OutPinId {
+            node: second,
+            output: 0,
+        };
+        connect(&mut snarl, second_out, upper_in);
+        assert_eq!(snarl.in_pin(upper_in).remotes, vec![second_out]);
+        assert_eq!(snarl.out_pin(first_out).remotes.len(), 1);
+    }
+
+    #[test]
+    fn test_multiple_inputs() {
+        let mut snarl = Snarl::new();
+        let first = snarl.insert_node(Pos2::ZERO, Node::input("first".into()));
+        let second = snarl.insert_node(Pos2::ZERO, Node::input("second".into()));
+        let upper = snarl.insert_node(
+            Pos2::ZERO,
+            Node::transform(Transformer::Uppercase, Data::Text(String::new())),
+        );
+        let upper_in = InPinId {
+            node: upper,
+            input: 0,
+        };
+        for node in [first, second] {
+            snarl.connect(OutPinId { node, output: 0 }, upper_in);
+        }
+
+        let remotes = snarl.in_pin(upper_in).remotes;
+        assert_eq!(remotes.len(), 2);
+        let input = input_data(&snarl, &remotes).unwrap();
+        assert_eq!(input, Data::Error("multiple inputs not supported".into()));
+        snarl[upper].evaluate(&input);
+        assert!(matches!(snarl[upper].data(), Data::Error(_)));
+    }
+
+    #[test]
+    fn test_collect() {
+        let mut snarl = Snarl::new();
+        let first = snarl.insert_node(Pos2::ZERO, Node::input("first".into()));
+        let second = snarl.insert_node(Pos2::ZERO, Node::input("second".into()));
+        let node = snarl.insert_node(Pos2::ZERO, Node::collect());
+        for (input, from) in [first, second].into_iter().enumerate() {
+            connect(
+                &mut snarl,
+                OutPinId {
+                    node: from,
+                    output: 0,
+                },
+                InPinId { node, input },
+            );
+        }
+
+        collect(&mut snarl, node);
+        assert_eq!(
+            snarl[node].data(),
+            Data::List(vec![
+                Data::Text("first".into()),
+                Data::Text("second".into())
+            ])
+        );
+        assert!(matches!(snarl[node], Node::Collect { inputs: 3, .. }));
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let mut node = Node::snapshot();
+        node.evaluate(&Data::Text("first".into()));
+        assert_eq!(node.output(0), Data::Text("first".into()));
+
+        node.capture();
+        node.evaluate(&Data::Text("second".into()));
+        assert_eq!(node.output(0), Data::Text("first".into()));
+
+        let node: Node = serde_json::from_str(&serde_json::to_string(&node).unwrap()).unwrap();
+        assert_eq!(node.output(0), Data::Text("first".into()));
+
+        let mut node = node;
+        node.release();
+        assert_eq!(node.output(0), Data::Text("second".into()));
+    }
+
+    #[test]
+    fn test_bytes_chain() {
+        // Not valid UTF-8, so any lossy conversion along the way would show
+        let bytes = vec![0x89, b'P', b'N', b'G', 0xff, 0x00, 0xfe];
+        let mut source = Node::snapshot();
+        source.evaluate(&Data::from_bytes(bytes.clone()));
+        source.capture();
+
+        let mut snarl = Snarl::new();
+        let source = snarl.insert_node(Pos2::ZERO, source);
+        let encode = snarl.insert_node(
+            Pos2::ZERO,
+            Node::transform(
+                Transformer::Encode {
+                    encoding: Encoding::Base64,
+                },
+                Data::Text(String::new()),
+            ),
+        );
+        let decode = snarl.insert_node(
+            Pos2::ZERO,
+            Node::transform(
+                Transformer::Decode {
+                    encoding: Encoding::Base64,
+                },
+                Data::Text(String::new()),
+            ),
+        );
+        for (from, to) in [(source, encode), (encode, decode)] {
+            connect(
+                &mut snarl,
+                OutPinId {
+                    node: from,
+                    output: 0,
+                },
+                InPinId { node: to, input: 0 },
+            );
+        }
+        for node in [encode, decode] {
+            let remotes = snarl.in_pin(InPinId { node, input: 0 }).remotes;
+            let input = input_data(&snarl, &remotes).unwrap();
+            snarl[node].evaluate(&input);
+        }
+
+        assert_eq!(
+            snarl[encode].data(),
+            Data::Text(BASE64_STANDARD.encode(&bytes))
+        );
+        assert_eq!(snarl[decode].data(), Data::Bytes(bytes));
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut node = Node::transform(
+            Transformer::Split {
+                pattern: "needle".into(),
+            },
+            Data::Text(String::new()),
+        );
+        assert!(node.matches("NEEDLE"));
+        assert!(!node.matches("haystack"));
+
+        node.evaluate(&Data::Text("haystack".into()));
+        assert!(node.matches("haystack"));
+        assert!(Node::input("Sample Text".into()).matches("sample"));
+    }
 }
@@ -0,0 +1,88 @@
+use std::io::{Read, Write};
+
+use base64::prelude::*;
+use egui_snarl::Snarl;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+
+use crate::node::Node;
+
+/// Longest link we hand out, so it still fits in a browser's address bar
+const MAX_LINK_LEN: usize = 64 * 1024;
+/// Largest graph we accept when importing, to not get blown up by a crafted link
+const MAX_GRAPH_LEN: u64 = 16 * 1024 * 1024;
+
+/// Serializes, compresses and Base64-encodes the graph, for use as a URL fragment
+pub fn export(snarl: &Snarl<Node>) -> Result<String, String> {
+    let json = serde_json::to_vec(snarl).map_err(|err| err.to_string())?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&json).map_err(|err| err.to_string())?;
+    let compressed = encoder.finish().map_err(|err| err.to_string())?;
+    let link = BASE64_URL_SAFE_NO_PAD.encode(compressed);
+    if link.len() > MAX_LINK_LEN {
+        return Err(format!(
+            "Graph is too large to share as a link ({} KiB, at most {} KiB)",
+            link.len() / 1024,
+            MAX_LINK_LEN / 1024
+        ));
+    }
+    Ok(link)
+}
+
+/// Reverses [`export`]; also takes a whole URL, with the graph after the `#`
+pub fn import(link: &str) -> Result<Snarl<Node>, String> {
+    let link = link.trim();
+    let encoded = link.rsplit_once('#').map_or(link, |(_, fragment)| fragment);
+    let compressed = BASE64_URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|_| "Not a graph link")?;
+    let mut json = Vec::new();
+    DeflateDecoder::new(compressed.as_slice())
+        .take(MAX_GRAPH_LEN + 1)
+        .read_to_end(&mut json)
+        .map_err(|_| "Not a graph link")?;
+    if json.len() as u64 > MAX_GRAPH_LEN {
+        return Err("Graph is too large".into());
+    }
+    serde_json::from_slice(&json).map_err(|err| format!("Invalid graph: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::pos2;
+    use egui_snarl::{InPinId, OutPinId};
+
+    use super::*;
+    use crate::node::Data;
+    use crate::transform::Transformer;
+
+    #[test]
+    fn test_share_round_trip() {
+        let mut snarl = Snarl::new();
+        let input = snarl.insert_node(pos2(0.0, 0.0), Node::input("a b".into()));
+        let upper = snarl.insert_node(
+            pos2(100.0, 0.0),
+            Node::transform(Transformer::Uppercase, Data::Text(String::new())),
+        );
+        snarl.connect(
+            OutPinId {
+                node: input,
+                output: 0,
+            },
+            InPinId {
+                node: upper,
+                input: 0,
+            },
+        );
+
+        let link = export(&snarl).unwrap();
+        let imported = import(&format!("https://example.com/#{link}")).unwrap();
+        assert_eq!(
+            serde_json::to_string(&imported).unwrap(),
+            serde_json::to_string(&snarl).unwrap()
+        );
+
+        assert_eq!(import("not a link!").err(), Some("Not a graph link".into()));
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashMap};
+
+use egui_snarl::{NodeId, Snarl, ui::SnarlViewer};
+
+use crate::node::{Node, NodeViewer};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct GraphStats {
+    pub nodes: usize,
+    pub connections: usize,
+    /// Number of nodes in the longest chain
+    pub depth: usize,
+    pub kinds: BTreeMap<String, usize>,
+}
+
+impl GraphStats {
+    pub fn new(snarl: &Snarl<Node>) -> Self {
+        let mut stats = GraphStats::default();
+        let mut upstream: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for (from, to) in snarl.wires() {
+            stats.connections += 1;
+            upstream.entry(to.node).or_default().push(from.node);
+        }
+
+        let mut depths = HashMap::new();
+        for (id, node) in snarl.node_ids() {
+            stats.nodes += 1;
+            *stats
+                .kinds
+                .entry(NodeViewer::default().title(node))
+                .or_default() += 1;
+            stats.depth = stats.depth.max(depth(id, &upstream, &mut depths));
+        }
+        stats
+    }
+}
+
+fn depth(
+    node: NodeId,
+    upstream: &HashMap<NodeId, Vec<NodeId>>,
+    depths: &mut HashMap<NodeId, Option<usize>>,
+) -> usize {
+    match depths.get(&node) {
+        Some(Some(depth)) => return *depth,
+        // Already being visited further down the stack, so this is a cycle
+        Some(None) => return 0,
+        None => (),
+    }
+    depths.insert(node, None);
+    let depth = 1 + upstream.get(&node).map_or(0, |parents| {
+        parents
+            .iter()
+            .map(|&parent| depth(parent, upstream, depths))
+            .max()
+            .unwrap_or(0)
+    });
+    depths.insert(node, Some(depth));
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::pos2;
+    use egui_snarl::{InPinId, OutPinId};
+
+    use super::*;
+    use crate::node::Data;
+    use crate::transform::Transformer;
+
+    #[test]
+    fn test_graph_stats() {
+        let mut snarl = Snarl::new();
+        let input = snarl.insert_node(pos2(0.0, 0.0), Node::input("a b".into()));
+        let split = snarl.insert_node(
+            pos2(0.0, 0.0),
+            Node::transform(
+                Transformer::Split {
+                    pattern: " ".into(),
+                },
+                Data::List(Vec::new()),
+            ),
+        );
+        let upper = snarl.insert_node(
+            pos2(0.0, 0.0),
+            Node::transform(Transformer::Uppercase, Data::List(Vec::new())),
+        );
+        snarl.insert_node(pos2(0.0, 0.0), Node::input(String::new()));
+        snarl.connect(
+            OutPinId {
+                node: input,
+                output: 0,
+            },
+            InPinId {
+                node: split,
+                input: 0,
+            },
+        );
+        snarl.connect(
+            OutPinId {
+                node: split,
+                output: 0,
+            },
+            InPinId {
+                node: upper,
+                input: 0,
+            },
+        );
+
+        assert_eq!(
+            GraphStats::new(&snarl),
+            GraphStats {
+                nodes: 4,
+                connections: 2,
+                depth: 3,
+                kinds: BTreeMap::from([
+                    ("Input".into(), 2),
+                    ("Split".into(), 1),
+                    ("Uppercase".into(), 1),
+                ]),
+            }
+        );
+    }
+}
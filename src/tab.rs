@@ -0,0 +1,52 @@
+use egui_snarl::Snarl;
+use serde::{Deserialize, Serialize};
+
+use crate::group::Group;
+use crate::node::Node;
+
+/// A graph of its own, with its groups, that can be switched to
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub struct Tab {
+    pub name: String,
+    pub snarl: Snarl<Node>,
+    pub groups: Vec<Group>,
+}
+
+impl Tab {
+    /// Independent copy of the whole graph, to experiment on without touching this one
+    pub fn duplicate(&self) -> Self {
+        Self {
+            name: format!("{} (copy)", self.name),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Pos2;
+
+    use super::*;
+    use crate::node::Data;
+
+    #[test]
+    fn test_duplicate() {
+        let mut tab = Tab {
+            name: "Graph".into(),
+            ..Default::default()
+        };
+        let node = tab
+            .snarl
+            .insert_node(Pos2::ZERO, Node::input("original".into()));
+
+        let mut copy = tab.duplicate();
+        assert_eq!(copy.name, "Graph (copy)");
+        copy.snarl[node] = Node::input("changed".into());
+        copy.snarl
+            .insert_node(Pos2::ZERO, Node::input("added".into()));
+
+        assert_eq!(tab.snarl[node].data(), Data::Text("original".into()));
+        assert_eq!(tab.snarl.nodes().count(), 1);
+        assert_eq!(copy.snarl.nodes().count(), 2);
+    }
+}
@@ -1,37 +1,788 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+use std::net::Ipv4Addr;
+
+use base64::alphabet::Alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
 use base64::prelude::*;
+use chardetng::{EncodingDetector, Iso2022JpDetection, Utf8Detection};
+use chrono::{DateTime, SecondsFormat};
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use image::{ImageFormat, ImageReader};
+use num_bigint::BigUint;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::node::Data;
 
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
 pub enum Transformer {
-    Split { pattern: String },
-    Join { separator: String },
-    Find { pattern: String },
-    Replace { pattern: String, replacer: String },
-    Slice { from: usize, to: usize },
-    Encode { encoding: Encoding },
-    Decode { encoding: Encoding },
+    Split {
+        pattern: String,
+    },
+    Join {
+        separator: String,
+        /// Put before each element
+        #[serde(default)]
+        prefix: String,
+        /// Put after each element
+        #[serde(default)]
+        suffix: String,
+    },
+    Find {
+        pattern: String,
+        #[serde(default)]
+        flatten: bool,
+    },
+    Replace {
+        pattern: String,
+        replacer: String,
+    },
+    Slice {
+        from: usize,
+        to: usize,
+    },
+    Encode {
+        encoding: Encoding,
+    },
+    Decode {
+        encoding: Encoding,
+    },
     Uppercase,
     Lowercase,
+    Checksum {
+        algo: ChecksumAlgo,
+    },
+    FormDecode,
+    StripHtml {
+        decode_entities: bool,
+    },
+    NumberFormat {
+        thousands: bool,
+        decimals: Option<usize>,
+    },
+    MultiReplace {
+        rules: Vec<(String, String)>,
+    },
+    SortChars {
+        descending: bool,
+    },
+    UnixTime {
+        to_date: bool,
+    },
+    SwapEndian {
+        word_size: usize,
+    },
+    /// Uses the 26-letter variant, where I/J and U/V get distinct codes
+    Bacon {
+        decode: bool,
+    },
+    ExtractEntities {
+        kind: EntityKind,
+    },
+    Rotations,
+    Not,
+    DetectCharset,
+    Transcode {
+        from: CharsetName,
+    },
+    RegexEscape,
+    /// Emits all `k`-permutations of the elements, or full ones when `k` is unset
+    Permutations {
+        k: Option<usize>,
+    },
+    /// Works on numbers written as text, since there's no numeric data type
+    Math {
+        func: MathFunc,
+    },
+    /// Ignores its input, and lists the numbers from `start` to `end` inclusive
+    Range {
+        start: i64,
+        end: i64,
+        step: i64,
+    },
+    /// Shifts letters by `shift` places, and digits too (mod 10) if `digits` is set
+    Caesar {
+        shift: i32,
+        #[serde(default)]
+        digits: bool,
+    },
+    Hmac {
+        algo: HashAlgorithm,
+        key: String,
+    },
+    ConfigConvert {
+        from: ConfigFmt,
+        to: ConfigFmt,
+    },
+    /// Only decodes the percent-escapes listed in `escapes`, like `%20 %3D`
+    PartialUrlDecode {
+        escapes: String,
+    },
+    /// Ranks likely key lengths of a repeating-key XOR, best first
+    GuessXorKeyLen {
+        max: usize,
+    },
+    /// Tries every single-byte XOR key, best English-looking results first
+    SingleByteXor,
+    CollapseWhitespace,
+    /// Cuts the text into fields of these many characters, plus whatever is left over
+    FixedWidth {
+        widths: Vec<usize>,
+    },
+    /// Ignores its input, and lists the first `count` terms of a sequence
+    Sequence {
+        kind: SeqKind,
+        count: usize,
+    },
+    /// Base64 with a scrambled alphabet of 64 characters, optionally followed by a padding character
+    Base64Custom {
+        alphabet: String,
+        decode: bool,
+    },
+    /// Computes the check digit to append, or checks the last digit if `verify` is set
+    Luhn {
+        verify: bool,
+    },
+    /// Decodes a binary serialization format into pretty-printed JSON
+    DecodeBinaryFormat {
+        fmt: BinFmt,
+    },
+    /// Converts a binary string to Gray code, or back if `to_gray` is unset
+    GrayCode {
+        to_gray: bool,
+    },
+    /// Each run of a character becomes the character followed by its length in decimal, like
+    /// `aaabb` <-> `a3b2`; digits can't be encoded, as they'd be mistaken for lengths
+    RunLength {
+        decode: bool,
+    },
+    /// Longest common prefix of a list's texts, or longest common substring if `substring` is set
+    CommonPrefix {
+        substring: bool,
+    },
+    /// Reads an IPv4 address as dotted decimal, an integer, or `0x`-prefixed hex
+    IpFormat {
+        to: IpFormat,
+    },
+    /// Evaluates arithmetic with `+ - * /` and parentheses
+    Eval,
+    /// Maps each character of `from` to the one at the same position in `to`, like `tr`
+    Translate {
+        from: String,
+        to: String,
+    },
+    /// Turns literal `\n`, `\t`, `\r`, `\0`, `\\` and escaped quotes into the characters they stand
+    /// for, or back if `escape` is set; unknown sequences are left alone
+    Unescape {
+        escape: bool,
+    },
+    LengthBreakdown,
+    SplitGraphemes,
+    /// Converts an integer between bases 2 to 36
+    BaseConvert {
+        from: u32,
+        to: u32,
+    },
+    /// 5x5 digraph cipher with I and J merged. Encrypting splits doubled letters and pads odd
+    /// lengths with X; decrypting leaves those in
+    Playfair {
+        key: String,
+        decrypt: bool,
+    },
+    /// Letters become their position in the alphabet, separated by spaces, with ` - ` between
+    /// words. Decoding also takes hyphen-separated numbers; other characters pass through
+    A1Z26 {
+        decode: bool,
+    },
+    /// Text between `start` and the next `end`: the first such text, which is an error if there's
+    /// none, or a list of all of them (possibly empty) if `all` is set
+    Between {
+        start: String,
+        end: String,
+        all: bool,
+    },
+    /// Tap code: each letter is its row and column in a 5x5 grid (with K sharing C's cell) as
+    /// runs of dots, e.g. `h` is `.. ...`. Letters are separated by two spaces and words by ` / `
+    TapCode {
+        decode: bool,
+    },
+    /// `base^exp mod modulus` of big integers, each taken from the input when left empty
+    ModExp {
+        base: String,
+        exp: String,
+        modulus: String,
+    },
+    /// Prime factors of a whole number, smallest first and repeated by multiplicity
+    Factorize,
+    /// Greatest common divisor, or least common multiple, of a list of whole numbers
+    GcdLcm {
+        lcm: bool,
+    },
+    /// Letters become regional indicator symbols, pairs of which render as flags (`US` → 🇺🇸);
+    /// everything else passes through
+    RegionalIndicators {
+        decode: bool,
+    },
+    /// Formats a number with a printf-style spec holding one `%[-+0][width][.precision]conv`
+    /// directive, where conv is one of `d i f x X o b`; `%%` is a literal percent sign
+    Printf {
+        spec: String,
+    },
+    /// Swaps the two hex digits of every byte in a hex string (`1234` → `2143`)
+    SwapNibbles,
+    /// Writes the text in rows as wide as the key and reads the columns off in the alphabetical
+    /// order of the key's letters (ties left to right), without padding the last row
+    ColumnarTransposition {
+        key: String,
+        decrypt: bool,
+    },
+    /// Swaps letters for look-alike digits following [`LEET`]. Decoding reverses it, so `1` is
+    /// always `l` (never `i`), and also reads `@` as `a`, `$` as `s` and `+` as `t`
+    Leet {
+        decode: bool,
+    },
+    /// Converts between a number of seconds and durations like `1d2h3m4s`, taking either as input
+    Duration {
+        to: DurationFmt,
+    },
+    /// Splits text into entries on `line_sep` (lines if empty) and each entry into a key and
+    /// value on the first `kv_sep`, giving a list of trimmed `key=value` texts. Entries without
+    /// the separator, like ini section headers and comments, are skipped
+    ParseKeyValue {
+        line_sep: String,
+        kv_sep: String,
+    },
+    /// Randomly upper- or lowercases each letter, the same way every time for a given seed
+    MockCase {
+        seed: u64,
+    },
+    /// The line numbered `index`, counting from 1 at the top, or at the bottom if `from_end` is set
+    Line {
+        index: usize,
+        from_end: bool,
+    },
+    /// Sorts the lines of a text, by value with any non-numeric lines after the numbers if
+    /// `numeric` is set
+    SortLines {
+        descending: bool,
+        numeric: bool,
+    },
+    /// Drops repeated lines, keeping the first of each, or only collapses runs of the same line
+    /// if `adjacent_only` is set (like `uniq`)
+    UniqueLines {
+        adjacent_only: bool,
+    },
+    /// Keeps the lines matching `pattern`, or the ones that don't if `invert` is set
+    Grep {
+        pattern: String,
+        invert: bool,
+    },
+    /// Sorts a list of texts in human order, comparing runs of digits by value (`img2` before
+    /// `img10`); other elements go last
+    NaturalSort {
+        descending: bool,
+    },
+    /// Writes a list as a Markdown bullet or numbered list, or a list of lists as a table whose
+    /// first row is the header
+    Markdown {
+        kind: MdKind,
+    },
+    /// Monoalphabetic substitution, `cipher_alphabet` being the 26 letters that `a` to `z` map to.
+    /// Case is kept and anything but letters passes through
+    Substitution {
+        cipher_alphabet: String,
+        decrypt: bool,
+    },
+    /// Removes a leading UTF-8 byte-order mark, or adds one if there isn't one yet
+    Bom {
+        add: bool,
+    },
+    /// SHA digest of the input, as hex
+    Hash {
+        algo: HashAlgorithm,
+    },
+    /// Joins the first, last or nth character of each element of a list, or each line of a
+    /// text; ones too short for it are skipped
+    Acrostic {
+        position: AcrosticPos,
+    },
+    /// Format, width and height of a PNG, JPEG or GIF image
+    ImageInfo,
+    /// Runs of at least `min_len` printable ASCII characters, like the Unix `strings` tool
+    Strings {
+        min_len: usize,
+    },
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum Encoding {
     Base64,
     Base64UrlSafe,
     URL,
+    Base58,
+    /// Internationalized domain names to and from their ASCII `xn--` form
+    Punycode,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum ChecksumAlgo {
+    Crc32,
+    Adler32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum ConfigFmt {
+    Json,
+    Yaml,
+    Toml,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum SeqKind {
+    Fibonacci,
+    Primes,
+    Squares,
+}
+
+pub const MAX_SEQUENCE_LEN: usize = 10_000;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum BinFmt {
+    MessagePack,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum IpFormat {
+    Dotted,
+    Integer,
+    Hex,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum DurationFmt {
+    Seconds,
+    Human,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum MdKind {
+    Bullets,
+    Numbered,
+    Table,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum AcrosticPos {
+    First,
+    Last,
+    /// Counting from 1
+    Nth(usize),
+}
+
+impl AcrosticPos {
+    fn pick(&self, text: &str) -> Option<char> {
+        match self {
+            AcrosticPos::First => text.chars().next(),
+            AcrosticPos::Last => text.chars().next_back(),
+            AcrosticPos::Nth(n) => text.chars().nth(n.checked_sub(1)?),
+        }
+    }
+}
+
+/// The kind of data a transformer is meant for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputKind {
+    /// Works on texts, and on each element of a list
+    Text,
+    /// Works on a list as a whole
+    List,
+    /// Doesn't care, whether because it ignores its input or also works on bytes
+    Any,
+}
+
+impl InputKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputKind::Text => "Text",
+            InputKind::List => "List",
+            InputKind::Any => "anything",
+        }
+    }
+
+    /// Whether the data is something a transformer of this kind does something useful with.
+    /// Errors are always accepted since they're just passed along, while bytes given to a text
+    /// transformer would only be read lossily
+    pub fn accepts(&self, data: &Data) -> bool {
+        match self {
+            InputKind::Text => match data {
+                Data::Bytes(_) => false,
+                Data::List(items) => items.iter().all(|item| self.accepts(item)),
+                Data::Text(_) | Data::Error(_) => true,
+            },
+            InputKind::List => matches!(data, Data::List(_) | Data::Error(_)),
+            InputKind::Any => true,
+        }
+    }
+}
+
+impl Encoding {
+    fn encode(&self, bytes: &[u8]) -> Data {
+        Data::Text(match self {
+            Encoding::Base64 => BASE64_STANDARD.encode(bytes),
+            Encoding::Base64UrlSafe => BASE64_URL_SAFE.encode(bytes),
+            Encoding::URL => urlencoding::encode_binary(bytes).into(),
+            Encoding::Base58 => bs58::encode(bytes).into_string(),
+            Encoding::Punycode => match idna::domain_to_ascii(&String::from_utf8_lossy(bytes)) {
+                Ok(ascii) => ascii,
+                Err(_) => return Data::Error("Not a valid domain name".into()),
+            },
+        })
+    }
+}
+
+impl HashAlgorithm {
+    fn hash(&self, bytes: &[u8]) -> String {
+        let digest = match self {
+            HashAlgorithm::Sha1 => Sha1::digest(bytes).to_vec(),
+            HashAlgorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+            HashAlgorithm::Sha512 => Sha512::digest(bytes).to_vec(),
+        };
+        digest.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn hmac(&self, key: &[u8], bytes: &[u8]) -> String {
+        fn mac<D: Mac + KeyInit>(key: &[u8], bytes: &[u8]) -> Vec<u8> {
+            let mut mac = <D as Mac>::new_from_slice(key).expect("HMAC takes keys of any size");
+            mac.update(bytes);
+            mac.finalize().into_bytes().to_vec()
+        }
+        let mac = match self {
+            HashAlgorithm::Sha1 => mac::<Hmac<Sha1>>(key, bytes),
+            HashAlgorithm::Sha256 => mac::<Hmac<Sha256>>(key, bytes),
+            HashAlgorithm::Sha512 => mac::<Hmac<Sha512>>(key, bytes),
+        };
+        mac.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+impl ConfigFmt {
+    pub const ALL: [ConfigFmt; 3] = [ConfigFmt::Json, ConfigFmt::Yaml, ConfigFmt::Toml];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConfigFmt::Json => "JSON",
+            ConfigFmt::Yaml => "YAML",
+            ConfigFmt::Toml => "TOML",
+        }
+    }
+
+    fn parse(&self, text: &str) -> Result<serde_json::Value, String> {
+        match self {
+            ConfigFmt::Json => serde_json::from_str(text).map_err(|err| err.to_string()),
+            ConfigFmt::Yaml => serde_yaml::from_str(text).map_err(|err| err.to_string()),
+            ConfigFmt::Toml => toml::from_str(text).map_err(|err| err.to_string()),
+        }
+    }
+
+    fn serialize(&self, value: &serde_json::Value) -> Result<String, String> {
+        match self {
+            ConfigFmt::Json => serde_json::to_string_pretty(value).map_err(|err| err.to_string()),
+            ConfigFmt::Yaml => serde_yaml::to_string(value).map_err(|err| err.to_string()),
+            ConfigFmt::Toml => toml::to_string(value).map_err(|err| err.to_string()),
+        }
+    }
+}
+
+impl SeqKind {
+    fn terms(&self, count: usize) -> Data {
+        if count > MAX_SEQUENCE_LEN {
+            return Data::Error("Sequence is too long".into());
+        }
+        let mut terms: Vec<u128> = Vec::with_capacity(count);
+        match self {
+            SeqKind::Fibonacci => {
+                let (mut a, mut b) = (Some(0u128), Some(1u128));
+                for _ in 0..count {
+                    let Some(term) = a else {
+                        return Data::Error(format!(
+                            "Fibonacci numbers overflow after {} terms",
+                            terms.len()
+                        ));
+                    };
+                    terms.push(term);
+                    (a, b) = (b, a.zip(b).and_then(|(a, b)| a.checked_add(b)));
+                }
+            }
+            SeqKind::Primes => {
+                let mut n = 2;
+                while terms.len() < count {
+                    if terms
+                        .iter()
+                        .take_while(|p| *p * *p <= n)
+                        .all(|p| n % p != 0)
+                    {
+                        terms.push(n);
+                    }
+                    n += 1;
+                }
+            }
+            SeqKind::Squares => terms.extend((1..=count as u128).map(|n| n * n)),
+        }
+        Data::List(terms.iter().map(|n| Data::Text(n.to_string())).collect())
+    }
+}
+
+impl BinFmt {
+    fn decode(&self, bytes: &[u8]) -> Data {
+        let value: Result<serde_json::Value, _> = match self {
+            BinFmt::MessagePack => rmp_serde::from_slice(bytes),
+        };
+        match value {
+            Ok(value) => {
+                Data::Text(serde_json::to_string_pretty(&value).expect("JSON values serialize"))
+            }
+            Err(_) => Data::Error("Invalid MessagePack data".into()),
+        }
+    }
+}
+
+impl ChecksumAlgo {
+    fn checksum(&self, bytes: &[u8]) -> String {
+        let checksum = match self {
+            ChecksumAlgo::Crc32 => crc32fast::hash(bytes),
+            ChecksumAlgo::Adler32 => adler2::adler32_slice(bytes),
+        };
+        format!("{checksum:08x}")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum CharsetName {
+    Windows1252,
+    Iso8859_2,
+    Koi8R,
+    ShiftJis,
+    EucJp,
+    Gbk,
+    Big5,
+    EucKr,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl CharsetName {
+    pub const ALL: [CharsetName; 10] = [
+        CharsetName::Windows1252,
+        CharsetName::Iso8859_2,
+        CharsetName::Koi8R,
+        CharsetName::ShiftJis,
+        CharsetName::EucJp,
+        CharsetName::Gbk,
+        CharsetName::Big5,
+        CharsetName::EucKr,
+        CharsetName::Utf16Le,
+        CharsetName::Utf16Be,
+    ];
+
+    fn encoding(&self) -> &'static encoding_rs::Encoding {
+        match self {
+            CharsetName::Windows1252 => encoding_rs::WINDOWS_1252,
+            CharsetName::Iso8859_2 => encoding_rs::ISO_8859_2,
+            CharsetName::Koi8R => encoding_rs::KOI8_R,
+            CharsetName::ShiftJis => encoding_rs::SHIFT_JIS,
+            CharsetName::EucJp => encoding_rs::EUC_JP,
+            CharsetName::Gbk => encoding_rs::GBK,
+            CharsetName::Big5 => encoding_rs::BIG5,
+            CharsetName::EucKr => encoding_rs::EUC_KR,
+            CharsetName::Utf16Le => encoding_rs::UTF_16LE,
+            CharsetName::Utf16Be => encoding_rs::UTF_16BE,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        self.encoding().name()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Data {
+        let (text, had_errors) = self.encoding().decode_without_bom_handling(bytes);
+        if had_errors {
+            Data::Error(format!("Invalid {} data", self.label()))
+        } else {
+            Data::Text(text.into())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum EntityKind {
+    Url,
+    Email,
+    Ipv4,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub enum MathFunc {
+    Sin,
+    Cos,
+    Tan,
+    Sqrt,
+    Ln,
+    Log10,
+    Abs,
+    ToRadians,
+    ToDegrees,
+}
+
+impl MathFunc {
+    pub const ALL: [MathFunc; 9] = [
+        MathFunc::Sin,
+        MathFunc::Cos,
+        MathFunc::Tan,
+        MathFunc::Sqrt,
+        MathFunc::Ln,
+        MathFunc::Log10,
+        MathFunc::Abs,
+        MathFunc::ToRadians,
+        MathFunc::ToDegrees,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MathFunc::Sin => "sin",
+            MathFunc::Cos => "cos",
+            MathFunc::Tan => "tan",
+            MathFunc::Sqrt => "sqrt",
+            MathFunc::Ln => "ln",
+            MathFunc::Log10 => "log10",
+            MathFunc::Abs => "abs",
+            MathFunc::ToRadians => "degrees → radians",
+            MathFunc::ToDegrees => "radians → degrees",
+        }
+    }
+
+    fn apply(&self, x: f64) -> f64 {
+        match self {
+            MathFunc::Sin => x.sin(),
+            MathFunc::Cos => x.cos(),
+            MathFunc::Tan => x.tan(),
+            MathFunc::Sqrt => x.sqrt(),
+            MathFunc::Ln => x.ln(),
+            MathFunc::Log10 => x.log10(),
+            MathFunc::Abs => x.abs(),
+            MathFunc::ToRadians => x.to_radians(),
+            MathFunc::ToDegrees => x.to_degrees(),
+        }
+    }
 }
 
 impl Transformer {
+    /// What the transformer expects as input; a list-only one given a text passes it through or
+    /// treats it as a single element
+    pub fn accepts(&self) -> InputKind {
+        match self {
+            Transformer::Join { .. }
+            | Transformer::CommonPrefix { .. }
+            | Transformer::GcdLcm { .. }
+            | Transformer::NaturalSort { .. }
+            | Transformer::Markdown { .. } => InputKind::List,
+            // Ignores its input, or takes anything
+            Transformer::Range { .. }
+            | Transformer::Sequence { .. }
+            | Transformer::Permutations { .. }
+            | Transformer::Acrostic { .. } => InputKind::Any,
+            // Also works on the bytes themselves
+            Transformer::Encode { .. }
+            | Transformer::Checksum { .. }
+            | Transformer::Hmac { .. }
+            | Transformer::Hash { .. }
+            | Transformer::Base64Custom { decode: false, .. }
+            | Transformer::GuessXorKeyLen { .. }
+            | Transformer::SingleByteXor
+            | Transformer::DecodeBinaryFormat { .. }
+            | Transformer::ImageInfo
+            | Transformer::Strings { .. }
+            | Transformer::Not
+            | Transformer::Bom { .. }
+            | Transformer::DetectCharset
+            | Transformer::Transcode { .. } => InputKind::Any,
+            Transformer::Split { .. }
+            | Transformer::Find { .. }
+            | Transformer::Replace { .. }
+            | Transformer::Slice { .. }
+            | Transformer::Decode { .. }
+            | Transformer::Uppercase
+            | Transformer::Lowercase
+            | Transformer::FormDecode
+            | Transformer::StripHtml { .. }
+            | Transformer::NumberFormat { .. }
+            | Transformer::MultiReplace { .. }
+            | Transformer::SortChars { .. }
+            | Transformer::UnixTime { .. }
+            | Transformer::SwapEndian { .. }
+            | Transformer::Bacon { .. }
+            | Transformer::ExtractEntities { .. }
+            | Transformer::Rotations
+            | Transformer::RegexEscape
+            | Transformer::Math { .. }
+            | Transformer::Caesar { .. }
+            | Transformer::ConfigConvert { .. }
+            | Transformer::PartialUrlDecode { .. }
+            | Transformer::CollapseWhitespace
+            | Transformer::FixedWidth { .. }
+            | Transformer::Luhn { .. }
+            | Transformer::GrayCode { .. }
+            | Transformer::RunLength { .. }
+            | Transformer::IpFormat { .. }
+            | Transformer::Eval
+            | Transformer::Translate { .. }
+            | Transformer::Unescape { .. }
+            | Transformer::LengthBreakdown
+            | Transformer::SplitGraphemes
+            | Transformer::BaseConvert { .. }
+            | Transformer::Playfair { .. }
+            | Transformer::A1Z26 { .. }
+            | Transformer::Between { .. }
+            | Transformer::TapCode { .. }
+            | Transformer::ModExp { .. }
+            | Transformer::Factorize
+            | Transformer::RegionalIndicators { .. }
+            | Transformer::Printf { .. }
+            | Transformer::SwapNibbles
+            | Transformer::ColumnarTransposition { .. }
+            | Transformer::Leet { .. }
+            | Transformer::Duration { .. }
+            | Transformer::ParseKeyValue { .. }
+            | Transformer::MockCase { .. }
+            | Transformer::Line { .. }
+            | Transformer::SortLines { .. }
+            | Transformer::UniqueLines { .. }
+            | Transformer::Grep { .. }
+            | Transformer::Substitution { .. }
+            | Transformer::Base64Custom { decode: true, .. } => InputKind::Text,
+        }
+    }
+
     pub fn transform(&self, data: &Data) -> Data {
         match data {
             Data::Text(text) => match self {
                 Transformer::Split { pattern } => {
                     Data::List(text.split(pattern).map(|p| Data::Text(p.into())).collect())
                 }
-                Transformer::Find { pattern } => {
+                Transformer::Find { pattern, .. } => {
                     if let Ok(re) = Regex::new(pattern) {
                         Data::List(
                             re.find_iter(text)
@@ -52,225 +803,3827 @@ impl Transformer {
                 Transformer::Slice { from, to } => {
                     Data::Text(text[(*from).min(text.len())..(*to).min(text.len())].into())
                 }
-                Transformer::Join { .. } => Data::Text(text.into()),
-                Transformer::Encode { encoding } => match encoding {
-                    Encoding::Base64 => Data::Text(BASE64_STANDARD.encode(text)),
-                    Encoding::Base64UrlSafe => Data::Text(BASE64_URL_SAFE.encode(text)),
-                    Encoding::URL => Data::Text(urlencoding::encode(text).into()),
-                },
+                Transformer::Join { prefix, suffix, .. } => {
+                    Data::Text(format!("{prefix}{text}{suffix}"))
+                }
+                Transformer::Encode { encoding } => encoding.encode(text.as_bytes()),
                 Transformer::Decode { encoding } => match encoding {
                     Encoding::Base64 => match BASE64_STANDARD.decode(text) {
-                        Ok(text) => Data::Text(String::from_utf8_lossy(&text).into()),
+                        Ok(bytes) => Data::from_bytes(bytes),
                         Err(err) => Data::Error(err.to_string()),
                     },
                     Encoding::Base64UrlSafe => match BASE64_URL_SAFE.decode(text) {
-                        Ok(text) => Data::Text(String::from_utf8_lossy(&text).into()),
+                        Ok(bytes) => Data::from_bytes(bytes),
                         Err(err) => Data::Error(err.to_string()),
                     },
-                    Encoding::URL => match urlencoding::decode(text) {
-                        Ok(text) => Data::Text(text.into()),
+                    Encoding::URL => {
+                        Data::from_bytes(urlencoding::decode_binary(text.as_bytes()).into())
+                    }
+                    Encoding::Base58 => match bs58::decode(text).into_vec() {
+                        Ok(bytes) => Data::from_bytes(bytes),
                         Err(err) => Data::Error(err.to_string()),
                     },
+                    Encoding::Punycode => match idna::domain_to_unicode(text) {
+                        (unicode, Ok(())) => Data::Text(unicode),
+                        (_, Err(_)) => Data::Error("Not a valid domain name".into()),
+                    },
                 },
                 Transformer::Uppercase => Data::Text(text.to_uppercase()),
                 Transformer::Lowercase => Data::Text(text.to_lowercase()),
+                Transformer::Checksum { algo } => Data::Text(algo.checksum(text.as_bytes())),
+                Transformer::FormDecode => {
+                    let decode =
+                        |s: &str| urlencoding::decode(&s.replace('+', " ")).map(String::from);
+                    let mut pairs = Vec::new();
+                    for pair in text.split('&').filter(|p| !p.is_empty()) {
+                        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                        match (decode(key), decode(value)) {
+                            (Ok(key), Ok(value)) => {
+                                pairs.push(Data::Text(format!("{key}={value}")))
+                            }
+                            (Err(err), _) | (_, Err(err)) => return Data::Error(err.to_string()),
+                        }
+                    }
+                    Data::List(pairs)
+                }
+                Transformer::StripHtml { decode_entities } => {
+                    let tags = Regex::new(r"(?is)<(script|style)\b.*?</(script|style)>|<[^>]*>")
+                        .expect("valid regex");
+                    let stripped = tags.replace_all(text, "");
+                    if *decode_entities {
+                        Data::Text(decode_html_entities(&stripped))
+                    } else {
+                        Data::Text(stripped.into())
+                    }
+                }
+                Transformer::NumberFormat {
+                    thousands,
+                    decimals,
+                } => match text.trim().parse::<f64>() {
+                    Ok(number) => {
+                        let formatted = match decimals {
+                            Some(decimals) => format!("{number:.decimals$}"),
+                            None => number.to_string(),
+                        };
+                        if *thousands {
+                            Data::Text(group_thousands(&formatted))
+                        } else {
+                            Data::Text(formatted)
+                        }
+                    }
+                    Err(_) => Data::Error("Not a number".into()),
+                },
+                Transformer::MultiReplace { rules } => {
+                    let mut text = text.clone();
+                    for (i, (pattern, replacer)) in rules.iter().enumerate() {
+                        match Regex::new(pattern) {
+                            Ok(re) => text = re.replace_all(&text, replacer).into(),
+                            Err(_) => {
+                                return Data::Error(format!("Invalid pattern in rule {}", i + 1));
+                            }
+                        }
+                    }
+                    Data::Text(text)
+                }
+                Transformer::SortChars { descending } => {
+                    let mut chars: Vec<char> = text.chars().collect();
+                    chars.sort_unstable();
+                    if *descending {
+                        chars.reverse();
+                    }
+                    Data::Text(chars.into_iter().collect())
+                }
+                Transformer::UnixTime { to_date: true } => match text.trim().parse::<i64>() {
+                    Ok(seconds) => match DateTime::from_timestamp(seconds, 0) {
+                        Some(date) => Data::Text(date.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                        None => Data::Error("Timestamp out of range".into()),
+                    },
+                    Err(_) => Data::Error("Not a timestamp".into()),
+                },
+                Transformer::UnixTime { to_date: false } => {
+                    match DateTime::parse_from_rfc3339(text.trim()) {
+                        Ok(date) => Data::Text(date.timestamp().to_string()),
+                        Err(err) => Data::Error(err.to_string()),
+                    }
+                }
+                Transformer::SwapEndian { word_size } => {
+                    let hex: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+                    if !hex.iter().all(char::is_ascii_hexdigit) {
+                        Data::Error("Not a hex string".into())
+                    } else if *word_size == 0 || !hex.len().is_multiple_of(word_size * 2) {
+                        Data::Error("Length is not a multiple of the word size".into())
+                    } else {
+                        Data::Text(
+                            hex.chunks(word_size * 2)
+                                .flat_map(|word| word.chunks(2).rev().flatten())
+                                .collect(),
+                        )
+                    }
+                }
+                Transformer::Bacon { decode: false } => Data::Text(
+                    text.to_ascii_uppercase()
+                        .bytes()
+                        .filter(u8::is_ascii_uppercase)
+                        .map(|c| {
+                            let n = c - b'A';
+                            (0..5)
+                                .rev()
+                                .map(|bit| if n >> bit & 1 == 0 { 'A' } else { 'B' })
+                                .collect::<String>()
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                Transformer::Bacon { decode: true } => {
+                    let bits: Vec<u8> = text
+                        .chars()
+                        .filter_map(|c| match c.to_ascii_uppercase() {
+                            'A' => Some(0),
+                            'B' => Some(1),
+                            _ => None,
+                        })
+                        .collect();
+                    if !bits.len().is_multiple_of(5) {
+                        return Data::Error("Length is not a multiple of 5".into());
+                    }
+                    let mut decoded = String::new();
+                    for group in bits.chunks(5) {
+                        match group.iter().fold(0, |n, bit| n << 1 | bit) {
+                            n @ 0..26 => decoded.push((b'A' + n) as char),
+                            _ => return Data::Error("Invalid Bacon group".into()),
+                        }
+                    }
+                    Data::Text(decoded)
+                }
+                Transformer::ExtractEntities { kind } => {
+                    let pattern = match kind {
+                        EntityKind::Url => r#"\b(?:https?|ftp)://[^\s<>"']*[^\s<>"'.,;:!?)\]]"#,
+                        EntityKind::Email => r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b",
+                        EntityKind::Ipv4 => {
+                            r"\b(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b"
+                        }
+                    };
+                    let re = Regex::new(pattern).expect("valid regex");
+                    Data::List(
+                        re.find_iter(text)
+                            .map(|m| Data::Text(m.as_str().into()))
+                            .collect(),
+                    )
+                }
+                Transformer::Rotations => {
+                    let chars: Vec<char> = text.chars().collect();
+                    Data::List(
+                        (0..chars.len())
+                            .map(|i| Data::Text(chars[i..].iter().chain(&chars[..i]).collect()))
+                            .collect(),
+                    )
+                }
+                Transformer::Not => Data::from_bytes(text.bytes().map(|b| !b).collect()),
+                Transformer::DetectCharset => detect_charset(text.as_bytes()),
+                Transformer::Transcode { from } => from.decode(text.as_bytes()),
+                Transformer::RegexEscape => Data::Text(regex::escape(text)),
+                Transformer::Permutations { k } => {
+                    let chars: Vec<char> = text.chars().collect();
+                    match permutations(&chars, k.unwrap_or(chars.len())) {
+                        Some(perms) => Data::List(
+                            perms
+                                .into_iter()
+                                .map(|p| Data::Text(p.into_iter().collect()))
+                                .collect(),
+                        ),
+                        None => Data::Error("Too many permutations".into()),
+                    }
+                }
+                Transformer::Math { func } => match text.trim().parse::<f64>() {
+                    Ok(number) => {
+                        let result = func.apply(number);
+                        if result.is_finite() {
+                            Data::Text(result.to_string())
+                        } else {
+                            Data::Error(format!("{} is undefined for {number}", func.label()))
+                        }
+                    }
+                    Err(_) => Data::Error("Not a number".into()),
+                },
+                Transformer::Range { start, end, step } => range(*start, *end, *step),
+                Transformer::Caesar { shift, digits } => Data::Text(
+                    text.chars()
+                        .map(|c| {
+                            let rotate = |base: u8, len: i32| {
+                                let offset = (c as u8 - base) as i32 + shift;
+                                (base + offset.rem_euclid(len) as u8) as char
+                            };
+                            match c {
+                                'a'..='z' => rotate(b'a', 26),
+                                'A'..='Z' => rotate(b'A', 26),
+                                '0'..='9' if *digits => rotate(b'0', 10),
+                                c => c,
+                            }
+                        })
+                        .collect(),
+                ),
+                Transformer::Hmac { algo, key } => {
+                    Data::Text(algo.hmac(key.as_bytes(), text.as_bytes()))
+                }
+                Transformer::ConfigConvert { from, to } => {
+                    match from.parse(text).and_then(|value| to.serialize(&value)) {
+                        Ok(converted) => Data::Text(converted),
+                        Err(err) => Data::Error(err),
+                    }
+                }
+                Transformer::PartialUrlDecode { escapes } => {
+                    let mut wanted = Vec::new();
+                    for escape in escapes.split(|c: char| c.is_whitespace() || c == ',') {
+                        if escape.is_empty() {
+                            continue;
+                        }
+                        let hex = escape.strip_prefix('%').unwrap_or(escape);
+                        match hex_byte(hex.as_bytes()) {
+                            Some(byte) => wanted.push(byte),
+                            None => return Data::Error(format!("Invalid escape {escape}")),
+                        }
+                    }
+                    let bytes = text.as_bytes();
+                    let mut decoded = Vec::with_capacity(bytes.len());
+                    let mut i = 0;
+                    while i < bytes.len() {
+                        let byte = (bytes[i] == b'%')
+                            .then(|| bytes.get(i + 1..i + 3))
+                            .flatten()
+                            .and_then(hex_byte)
+                            .filter(|byte| wanted.contains(byte));
+                        if let Some(byte) = byte {
+                            decoded.push(byte);
+                            i += 3;
+                        } else {
+                            decoded.push(bytes[i]);
+                            i += 1;
+                        }
+                    }
+                    Data::from_bytes(decoded)
+                }
+                Transformer::GuessXorKeyLen { max } => match ciphertext_bytes(text) {
+                    Some(bytes) => guess_xor_key_len(&bytes, *max),
+                    None => Data::Error("Expected bytes, hex or Base64".into()),
+                },
+                Transformer::SingleByteXor => match ciphertext_bytes(text) {
+                    Some(bytes) => single_byte_xor(&bytes),
+                    None => Data::Error("Expected bytes, hex or Base64".into()),
+                },
+                Transformer::CollapseWhitespace => {
+                    Data::Text(text.split_whitespace().collect::<Vec<_>>().join(" "))
+                }
+                Transformer::FixedWidth { widths } => {
+                    let mut rest = text.as_str();
+                    let mut fields = Vec::with_capacity(widths.len() + 1);
+                    for width in widths {
+                        let end = rest
+                            .char_indices()
+                            .nth(*width)
+                            .map_or(rest.len(), |(i, _)| i);
+                        let (field, tail) = rest.split_at(end);
+                        fields.push(Data::Text(field.into()));
+                        rest = tail;
+                    }
+                    if !rest.is_empty() {
+                        fields.push(Data::Text(rest.into()));
+                    }
+                    Data::List(fields)
+                }
+                Transformer::Sequence { kind, count } => kind.terms(*count),
+                Transformer::Base64Custom { alphabet, decode } => match custom_base64(alphabet) {
+                    Ok((engine, padding)) if *decode => {
+                        let text: String = text
+                            .trim()
+                            .chars()
+                            .map(|c| if c == padding { '=' } else { c })
+                            .collect();
+                        match engine.decode(text) {
+                            Ok(bytes) => Data::from_bytes(bytes),
+                            Err(_) => Data::Error("Invalid custom Base64".into()),
+                        }
+                    }
+                    Ok((engine, padding)) => {
+                        Data::Text(engine.encode(text).replace('=', &padding.to_string()))
+                    }
+                    Err(err) => Data::Error(err),
+                },
+                Transformer::Luhn { verify } => {
+                    let digits: Option<Vec<u32>> = text
+                        .chars()
+                        .filter(|c| !c.is_whitespace() && *c != '-')
+                        .map(|c| c.to_digit(10))
+                        .collect();
+                    match digits {
+                        Some(digits) if !digits.is_empty() => {
+                            if *verify {
+                                let (check, payload) = digits.split_last().expect("not empty");
+                                Data::Text((luhn_check_digit(payload) == *check).to_string())
+                            } else {
+                                Data::Text(luhn_check_digit(&digits).to_string())
+                            }
+                        }
+                        _ => Data::Error("Not a number".into()),
+                    }
+                }
+                Transformer::DecodeBinaryFormat { fmt } => match ciphertext_bytes(text) {
+                    Some(bytes) => fmt.decode(&bytes),
+                    None => Data::Error("Expected bytes, hex or Base64".into()),
+                },
+                Transformer::GrayCode { to_gray } => {
+                    let bits: Option<Vec<bool>> = text
+                        .trim()
+                        .chars()
+                        .map(|c| match c {
+                            '0' => Some(false),
+                            '1' => Some(true),
+                            _ => None,
+                        })
+                        .collect();
+                    let Some(bits) = bits else {
+                        return Data::Error("Not a binary string".into());
+                    };
+                    let mut previous = false;
+                    Data::Text(
+                        bits.iter()
+                            .map(|&bit| {
+                                let converted = bit ^ previous;
+                                previous = if *to_gray { bit } else { converted };
+                                if converted { '1' } else { '0' }
+                            })
+                            .collect(),
+                    )
+                }
+                Transformer::RunLength { decode: false } => {
+                    if text.chars().any(|c| c.is_ascii_digit()) {
+                        return Data::Error("Can't run-length encode digits".into());
+                    }
+                    let mut encoded = String::new();
+                    let mut chars = text.chars().peekable();
+                    while let Some(c) = chars.next() {
+                        let mut len = 1;
+                        while chars.next_if_eq(&c).is_some() {
+                            len += 1;
+                        }
+                        encoded.push(c);
+                        encoded.push_str(&len.to_string());
+                    }
+                    Data::Text(encoded)
+                }
+                Transformer::RunLength { decode: true } => {
+                    let mut decoded = String::new();
+                    let mut chars = text.chars().peekable();
+                    while let Some(c) = chars.next() {
+                        let mut len = String::new();
+                        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                            len.push(digit);
+                        }
+                        match len.parse::<usize>() {
+                            _ if len.is_empty() => {
+                                return Data::Error(format!("Invalid run length after {c}"));
+                            }
+                            Ok(0) => return Data::Error(format!("Invalid run length after {c}")),
+                            Ok(len)
+                                if len
+                                    .checked_mul(c.len_utf8())
+                                    .and_then(|bytes| bytes.checked_add(decoded.len()))
+                                    .is_some_and(|total| total <= MAX_RUN_LENGTH_OUTPUT) =>
+                            {
+                                decoded.extend(std::iter::repeat_n(c, len));
+                            }
+                            // Including counts with too many digits for a usize
+                            _ => return Data::Error("Output is too long".into()),
+                        }
+                    }
+                    Data::Text(decoded)
+                }
+                Transformer::CommonPrefix { .. } => Data::Text(text.clone()),
+                Transformer::IpFormat { to } => {
+                    let text = text.trim();
+                    let ip = if text.contains('.') {
+                        text.parse::<Ipv4Addr>().ok().map(u32::from)
+                    } else if let Some(hex) = text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else {
+                        text.parse::<u32>().ok()
+                    };
+                    match ip {
+                        Some(ip) => Data::Text(match to {
+                            IpFormat::Dotted => Ipv4Addr::from(ip).to_string(),
+                            IpFormat::Integer => ip.to_string(),
+                            IpFormat::Hex => format!("0x{ip:08x}"),
+                        }),
+                        None => Data::Error("Not an IPv4 address".into()),
+                    }
+                }
+                Transformer::Eval => match eval_arithmetic(text) {
+                    Ok(result) => Data::Text(result.to_string()),
+                    Err(err) => Data::Error(err),
+                },
+                Transformer::Translate { from, to } => {
+                    if from.chars().count() != to.chars().count() {
+                        return Data::Error("From and to differ in length".into());
+                    }
+                    let table: HashMap<char, char> = from.chars().zip(to.chars()).collect();
+                    Data::Text(text.chars().map(|c| *table.get(&c).unwrap_or(&c)).collect())
+                }
+                Transformer::Unescape { escape: false } => {
+                    let mut unescaped = String::with_capacity(text.len());
+                    let mut chars = text.chars();
+                    while let Some(c) = chars.next() {
+                        if c != '\\' {
+                            unescaped.push(c);
+                            continue;
+                        }
+                        match chars.next() {
+                            Some('n') => unescaped.push('\n'),
+                            Some('t') => unescaped.push('\t'),
+                            Some('r') => unescaped.push('\r'),
+                            Some('0') => unescaped.push('\0'),
+                            Some(c @ ('\\' | '"' | '\'')) => unescaped.push(c),
+                            Some(c) => {
+                                unescaped.push('\\');
+                                unescaped.push(c);
+                            }
+                            None => unescaped.push('\\'),
+                        }
+                    }
+                    Data::Text(unescaped)
+                }
+                Transformer::Unescape { escape: true } => {
+                    let mut escaped = String::with_capacity(text.len());
+                    for c in text.chars() {
+                        match c {
+                            '\n' => escaped.push_str("\\n"),
+                            '\t' => escaped.push_str("\\t"),
+                            '\r' => escaped.push_str("\\r"),
+                            '\0' => escaped.push_str("\\0"),
+                            '\\' => escaped.push_str("\\\\"),
+                            c => escaped.push(c),
+                        }
+                    }
+                    Data::Text(escaped)
+                }
+                Transformer::LengthBreakdown => Data::List(vec![
+                    Data::Text(format!("bytes: {}", text.len())),
+                    Data::Text(format!("chars: {}", text.chars().count())),
+                    Data::Text(format!("graphemes: {}", text.graphemes(true).count())),
+                ]),
+                Transformer::SplitGraphemes => {
+                    Data::List(text.graphemes(true).map(|g| Data::Text(g.into())).collect())
+                }
+                Transformer::BaseConvert { from, to } => {
+                    if !(2..=36).contains(from) || !(2..=36).contains(to) {
+                        return Data::Error("Bases must be between 2 and 36".into());
+                    }
+                    let text = text.trim();
+                    let (sign, digits) = match text.strip_prefix('-') {
+                        Some(digits) => ("-", digits),
+                        None => ("", text),
+                    };
+                    // from_str_radix would also accept a second sign
+                    if digits.starts_with(['+', '-']) {
+                        return Data::Error(format!("Not a base {from} number"));
+                    }
+                    match u128::from_str_radix(digits, *from) {
+                        Ok(mut n) => {
+                            let mut converted = Vec::new();
+                            loop {
+                                let digit = char::from_digit((n % *to as u128) as u32, *to);
+                                converted.push(digit.expect("digit is below the base"));
+                                n /= *to as u128;
+                                if n == 0 {
+                                    break;
+                                }
+                            }
+                            Data::Text(sign.chars().chain(converted.into_iter().rev()).collect())
+                        }
+                        Err(_) => Data::Error(format!("Not a base {from} number")),
+                    }
+                }
+                Transformer::Playfair { key, decrypt } => playfair(key, text, *decrypt),
+                Transformer::A1Z26 { decode: false } => {
+                    let mut tokens = Vec::new();
+                    for word in text.split_whitespace() {
+                        if !tokens.is_empty() {
+                            tokens.push("-".to_string());
+                        }
+                        for c in word.chars() {
+                            if c.is_ascii_alphabetic() {
+                                tokens.push((c.to_ascii_lowercase() as u8 - b'a' + 1).to_string());
+                            } else {
+                                tokens.push(c.to_string());
+                            }
+                        }
+                    }
+                    Data::Text(tokens.join(" "))
+                }
+                Transformer::A1Z26 { decode: true } => {
+                    let mut decoded = String::new();
+                    for token in text.split_whitespace() {
+                        if token == "-" || token == "/" {
+                            decoded.push(' ');
+                            continue;
+                        }
+                        for part in token.split('-') {
+                            match part.parse::<u8>() {
+                                Ok(n @ 1..=26) => decoded.push((b'a' + n - 1) as char),
+                                Ok(n) => return Data::Error(format!("{n} is not a letter")),
+                                Err(_) => decoded.push_str(part),
+                            }
+                        }
+                    }
+                    Data::Text(decoded)
+                }
+                Transformer::Between { start, end, all } => {
+                    if start.is_empty() || end.is_empty() {
+                        return Data::Error("Delimiters can't be empty".into());
+                    }
+                    let mut found = Vec::new();
+                    let mut rest = text.as_str();
+                    while let Some((_, after_start)) = rest.split_once(start.as_str())
+                        && let Some((between, after_end)) = after_start.split_once(end.as_str())
+                    {
+                        found.push(Data::Text(between.into()));
+                        if !*all {
+                            break;
+                        }
+                        rest = after_end;
+                    }
+                    if *all {
+                        Data::List(found)
+                    } else {
+                        found
+                            .pop()
+                            .unwrap_or_else(|| Data::Error("Delimiters not found".into()))
+                    }
+                }
+                Transformer::TapCode { decode } => tap_code(text, *decode),
+                Transformer::ModExp { base, exp, modulus } => {
+                    let parse = |field: &str, name: &str| {
+                        let field = if field.trim().is_empty() { text } else { field };
+                        field
+                            .trim()
+                            .parse::<BigUint>()
+                            .map_err(|_| Data::Error(format!("Invalid {name}")))
+                    };
+                    let (base, exp, modulus) = match (
+                        parse(base, "base"),
+                        parse(exp, "exponent"),
+                        parse(modulus, "modulus"),
+                    ) {
+                        (Ok(base), Ok(exp), Ok(modulus)) => (base, exp, modulus),
+                        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return e,
+                    };
+                    if modulus == BigUint::ZERO {
+                        return Data::Error("Modulus can't be zero".into());
+                    }
+                    Data::Text(base.modpow(&exp, &modulus).to_string())
+                }
+                Transformer::Factorize => match text.trim().parse::<u64>() {
+                    Ok(number) => factorize(number),
+                    Err(_) => Data::Error("Not a whole number".into()),
+                },
+                Transformer::GcdLcm { lcm } => gcd_lcm(std::slice::from_ref(data), *lcm),
+                Transformer::RegionalIndicators { decode: false } => Data::Text(
+                    text.chars()
+                        .map(|c| {
+                            if c.is_ascii_alphabetic() {
+                                char::from_u32(
+                                    REGIONAL_INDICATOR_A
+                                        + (c.to_ascii_uppercase() as u32 - 'A' as u32),
+                                )
+                                .unwrap_or(c)
+                            } else {
+                                c
+                            }
+                        })
+                        .collect(),
+                ),
+                Transformer::RegionalIndicators { decode: true } => Data::Text(
+                    text.chars()
+                        .map(|c| match (c as u32).checked_sub(REGIONAL_INDICATOR_A) {
+                            Some(offset @ 0..26) => (b'A' + offset as u8) as char,
+                            _ => c,
+                        })
+                        .collect(),
+                ),
+                Transformer::Printf { spec } => printf(spec, text),
+                Transformer::SwapNibbles => {
+                    let text = text.trim();
+                    if !text.chars().all(|c| c.is_ascii_hexdigit()) {
+                        return Data::Error("Not hex".into());
+                    }
+                    if !text.len().is_multiple_of(2) {
+                        return Data::Error("Odd number of hex digits".into());
+                    }
+                    Data::Text(
+                        text.as_bytes()
+                            .chunks(2)
+                            .flat_map(|pair| [pair[1] as char, pair[0] as char])
+                            .collect(),
+                    )
+                }
+                Transformer::ColumnarTransposition { key, decrypt } => {
+                    columnar_transposition(key, text, *decrypt)
+                }
+                Transformer::Leet { decode: false } => Data::Text(
+                    text.chars()
+                        .map(|c| {
+                            LEET.iter()
+                                .find(|(letter, _)| *letter == c.to_ascii_lowercase())
+                                .map_or(c, |(_, digit)| *digit)
+                        })
+                        .collect(),
+                ),
+                Transformer::Leet { decode: true } => Data::Text(
+                    text.chars()
+                        .map(|c| match c {
+                            '@' => 'a',
+                            '$' => 's',
+                            '+' => 't',
+                            c => LEET
+                                .iter()
+                                .find(|(_, digit)| *digit == c)
+                                .map_or(c, |(letter, _)| *letter),
+                        })
+                        .collect(),
+                ),
+                Transformer::Duration { to } => {
+                    let text = text.trim();
+                    let Some(seconds) = parse_duration(text) else {
+                        return Data::Error("Not a duration".into());
+                    };
+                    Data::Text(match to {
+                        DurationFmt::Seconds => seconds.to_string(),
+                        DurationFmt::Human => format_duration(seconds),
+                    })
+                }
+                Transformer::ParseKeyValue { line_sep, kv_sep } => {
+                    if kv_sep.is_empty() {
+                        return Data::Error("Key/value separator can't be empty".into());
+                    }
+                    let entries: Vec<&str> = if line_sep.is_empty() {
+                        text.lines().collect()
+                    } else {
+                        text.split(line_sep.as_str()).collect()
+                    };
+                    Data::List(
+                        entries
+                            .into_iter()
+                            .filter_map(|entry| entry.split_once(kv_sep.as_str()))
+                            .map(|(key, value)| {
+                                Data::Text(format!("{}={}", key.trim(), value.trim()))
+                            })
+                            .collect(),
+                    )
+                }
+                Transformer::MockCase { seed } => {
+                    let mut state = *seed;
+                    Data::Text(
+                        text.chars()
+                            .map(|c| {
+                                if splitmix64(&mut state) & 1 == 0 {
+                                    c.to_lowercase().collect::<String>()
+                                } else {
+                                    c.to_uppercase().collect()
+                                }
+                            })
+                            .collect(),
+                    )
+                }
+                Transformer::Line { index, from_end } => {
+                    let lines: Vec<&str> = text.lines().collect();
+                    let line = match (index.checked_sub(1), from_end) {
+                        (Some(i), false) => lines.get(i),
+                        (Some(i), true) => {
+                            lines.len().checked_sub(i + 1).and_then(|i| lines.get(i))
+                        }
+                        (None, _) => None,
+                    };
+                    match line {
+                        Some(line) => Data::Text(line.to_string()),
+                        None => Data::Error(format!("No line {index} in {} lines", lines.len())),
+                    }
+                }
+                Transformer::SortLines {
+                    descending,
+                    numeric,
+                } => {
+                    let mut lines: Vec<&str> = text.lines().collect();
+                    if *numeric {
+                        lines.sort_by(|a, b| {
+                            match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                                (Ok(a), Ok(b)) => a.total_cmp(&b),
+                                (Ok(_), Err(_)) => Ordering::Less,
+                                (Err(_), Ok(_)) => Ordering::Greater,
+                                (Err(_), Err(_)) => a.cmp(b),
+                            }
+                        });
+                    } else {
+                        lines.sort();
+                    }
+                    if *descending {
+                        lines.reverse();
+                    }
+                    Data::Text(lines.join("\n"))
+                }
+                Transformer::UniqueLines { adjacent_only } => {
+                    let mut lines: Vec<&str> = text.lines().collect();
+                    if *adjacent_only {
+                        lines.dedup();
+                    } else {
+                        let mut seen = HashSet::new();
+                        lines.retain(|line| seen.insert(*line));
+                    }
+                    Data::Text(lines.join("\n"))
+                }
+                Transformer::Grep { pattern, invert } => {
+                    if let Ok(re) = Regex::new(pattern) {
+                        Data::Text(
+                            text.lines()
+                                .filter(|line| re.is_match(line) != *invert)
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                        )
+                    } else {
+                        Data::Error("Invalid pattern".into())
+                    }
+                }
+                Transformer::NaturalSort { .. } => Data::Text(text.clone()),
+                Transformer::Markdown { kind } => markdown(std::slice::from_ref(data), kind),
+                Transformer::Substitution {
+                    cipher_alphabet,
+                    decrypt,
+                } => {
+                    let cipher: Vec<u8> = cipher_alphabet
+                        .trim()
+                        .bytes()
+                        .map(|b| b.to_ascii_lowercase())
+                        .collect();
+                    let mut seen = HashSet::new();
+                    if cipher.len() != 26
+                        || !cipher
+                            .iter()
+                            .all(|b| b.is_ascii_lowercase() && seen.insert(*b))
+                    {
+                        return Data::Error("Cipher alphabet must have each letter once".into());
+                    }
+                    let (from, to): (&[u8], &[u8]) = if *decrypt {
+                        (&cipher, LOWERCASE)
+                    } else {
+                        (LOWERCASE, &cipher)
+                    };
+                    Data::Text(
+                        text.chars()
+                            .map(|c| {
+                                let Some(i) = from
+                                    .iter()
+                                    .position(|&b| b as char == c.to_ascii_lowercase())
+                                else {
+                                    return c;
+                                };
+                                if c.is_ascii_uppercase() {
+                                    to[i].to_ascii_uppercase() as char
+                                } else {
+                                    to[i] as char
+                                }
+                            })
+                            .collect(),
+                    )
+                }
+                Transformer::Bom { add } => {
+                    let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+                    if *add {
+                        Data::Text(format!("\u{FEFF}{text}"))
+                    } else {
+                        Data::Text(text.into())
+                    }
+                }
+                Transformer::Hash { algo } => Data::Text(algo.hash(text.as_bytes())),
+                Transformer::Acrostic { position } => Data::Text(
+                    text.lines()
+                        .filter_map(|line| position.pick(line))
+                        .collect(),
+                ),
+                Transformer::ImageInfo => match ciphertext_bytes(text) {
+                    Some(bytes) => image_info(&bytes),
+                    None => Data::Error("Expected bytes, hex or Base64".into()),
+                },
+                Transformer::Strings { min_len } => strings(text.as_bytes(), *min_len),
+            },
+            Data::List(data_vec) => match self {
+                Transformer::Join {
+                    separator,
+                    prefix,
+                    suffix,
+                } => {
+                    let mut texts = Vec::new();
+                    fn collect(d: &Data, out: &mut Vec<String>) -> Option<String> {
+                        match d {
+                            Data::Text(t) => {
+                                out.push(t.into());
+                                None
+                            }
+                            Data::Bytes(bytes) => {
+                                out.push(String::from_utf8_lossy(bytes).into());
+                                None
+                            }
+                            Data::Error(_) => Some("Input error".into()),
+                            Data::List(list) => {
+                                for item in list {
+                                    if let Some(err) = collect(item, out) {
+                                        return Some(err);
+                                    }
+                                }
+                                None
+                            }
+                        }
+                    }
+                    if let Some(err) = collect(data, &mut texts) {
+                        Data::Error(err)
+                    } else {
+                        let wrapped: Vec<String> = texts
+                            .iter()
+                            .map(|text| format!("{prefix}{text}{suffix}"))
+                            .collect();
+                        Data::Text(wrapped.join(separator))
+                    }
+                }
+                Transformer::Find { flatten: true, .. } => {
+                    let mut matches = Vec::new();
+                    for (i, d) in data_vec.iter().enumerate() {
+                        match self.transform(d) {
+                            Data::List(found) => matches
+                                .extend(found.iter().map(|m| Data::Text(format!("{i}: {m:?}")))),
+                            other => return other,
+                        }
+                    }
+                    Data::List(matches)
+                }
+                Transformer::Range { start, end, step } => range(*start, *end, *step),
+                Transformer::CommonPrefix { substring } => {
+                    let mut texts = Vec::with_capacity(data_vec.len());
+                    for d in data_vec {
+                        match d {
+                            Data::Text(text) => texts.push(text.chars().collect::<Vec<_>>()),
+                            Data::Error(_) => return Data::Error("Input error".into()),
+                            _ => (),
+                        }
+                    }
+                    if *substring {
+                        Data::Text(common_substring(&texts))
+                    } else {
+                        Data::Text(common_prefix(&texts))
+                    }
+                }
+                Transformer::Sequence { kind, count } => kind.terms(*count),
+                Transformer::GcdLcm { lcm } => gcd_lcm(data_vec, *lcm),
+                Transformer::Acrostic { position } => {
+                    let mut picked = String::new();
+                    for d in data_vec {
+                        match d {
+                            Data::Text(text) => picked.extend(position.pick(text)),
+                            Data::Error(_) => return Data::Error("Input error".into()),
+                            _ => (),
+                        }
+                    }
+                    Data::Text(picked)
+                }
+                Transformer::Markdown { kind } => markdown(data_vec, kind),
+                Transformer::NaturalSort { descending } => {
+                    let mut sorted = data_vec.clone();
+                    sorted.sort_by(|a, b| match (a, b) {
+                        (Data::Text(a), Data::Text(b)) => natural_cmp(a, b),
+                        (Data::Text(_), _) => Ordering::Less,
+                        (_, Data::Text(_)) => Ordering::Greater,
+                        _ => Ordering::Equal,
+                    });
+                    if *descending {
+                        sorted.reverse();
+                    }
+                    Data::List(sorted)
+                }
+                Transformer::Permutations { k } => {
+                    match permutations(data_vec, k.unwrap_or(data_vec.len())) {
+                        Some(perms) => Data::List(perms.into_iter().map(Data::List).collect()),
+                        None => Data::Error("Too many permutations".into()),
+                    }
+                }
+                _ => Data::List(data_vec.iter().map(|d| self.transform(d)).collect()),
+            },
+            Data::Bytes(bytes) => match self {
+                Transformer::Encode { encoding } => encoding.encode(bytes),
+                Transformer::Checksum { algo } => Data::Text(algo.checksum(bytes)),
+                Transformer::Hmac { algo, key } => Data::Text(algo.hmac(key.as_bytes(), bytes)),
+                Transformer::Hash { algo } => Data::Text(algo.hash(bytes)),
+                Transformer::Base64Custom {
+                    alphabet,
+                    decode: false,
+                } => match custom_base64(alphabet) {
+                    Ok((engine, padding)) => {
+                        Data::Text(engine.encode(bytes).replace('=', &padding.to_string()))
+                    }
+                    Err(err) => Data::Error(err),
+                },
+                Transformer::GuessXorKeyLen { max } => guess_xor_key_len(bytes, *max),
+                Transformer::SingleByteXor => single_byte_xor(bytes),
+                Transformer::DecodeBinaryFormat { fmt } => fmt.decode(bytes),
+                Transformer::ImageInfo => image_info(bytes),
+                Transformer::Strings { min_len } => strings(bytes, *min_len),
+                Transformer::Not => Data::from_bytes(bytes.iter().map(|b| !b).collect()),
+                Transformer::Bom { add } => {
+                    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+                    if *add {
+                        Data::from_bytes([UTF8_BOM, bytes].concat())
+                    } else {
+                        Data::from_bytes(bytes.to_vec())
+                    }
+                }
+                Transformer::DetectCharset => detect_charset(bytes),
+                Transformer::Transcode { from } => from.decode(bytes),
+                // Everything else only understands text
+                _ => self.transform(&Data::Text(String::from_utf8_lossy(bytes).into())),
+            },
+            Data::Error(_) => Data::Error("Input error".into()),
+        }
+    }
+}
+
+fn luhn_check_digit(payload: &[u32]) -> u32 {
+    let sum: u32 = payload
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| match (i % 2 == 0, d * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => *d,
+        })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+fn common_prefix(texts: &[Vec<char>]) -> String {
+    let Some((first, rest)) = texts.split_first() else {
+        return String::new();
+    };
+    let len = rest.iter().fold(first.len(), |len, text| {
+        first
+            .iter()
+            .zip(text)
+            .take(len)
+            .take_while(|(a, b)| a == b)
+            .count()
+    });
+    first[..len].iter().collect()
+}
+
+fn common_substring(texts: &[Vec<char>]) -> String {
+    let Some(shortest) = texts.iter().min_by_key(|text| text.len()) else {
+        return String::new();
+    };
+    for len in (1..=shortest.len()).rev() {
+        for candidate in shortest.windows(len) {
+            if texts
+                .iter()
+                .all(|text| text.windows(len).any(|window| window == candidate))
+            {
+                return candidate.iter().collect();
+            }
+        }
+    }
+    String::new()
+}
+
+const MAX_EVAL_DEPTH: usize = 256;
+
+/// Recursive descent over `expr = term (('+' | '-') term)*`, `term = factor (('*' | '/') factor)*`
+/// and `factor = '-' factor | '(' expr ')' | number`
+fn eval_arithmetic(text: &str) -> Result<f64, String> {
+    struct Parser<'a> {
+        chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+        text: &'a str,
+        depth: usize,
+    }
+
+    impl Parser<'_> {
+        fn peek(&mut self) -> Option<char> {
+            while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+            self.chars.peek().map(|(_, c)| *c)
+        }
+
+        fn expr(&mut self) -> Result<f64, String> {
+            let mut value = self.term()?;
+            loop {
+                match self.peek() {
+                    Some('+') => {
+                        self.chars.next();
+                        value += self.term()?;
+                    }
+                    Some('-') => {
+                        self.chars.next();
+                        value -= self.term()?;
+                    }
+                    _ => return Ok(value),
+                }
+            }
+        }
+
+        fn term(&mut self) -> Result<f64, String> {
+            let mut value = self.factor()?;
+            loop {
+                match self.peek() {
+                    Some('*') => {
+                        self.chars.next();
+                        value *= self.factor()?;
+                    }
+                    Some('/') => {
+                        self.chars.next();
+                        let divisor = self.factor()?;
+                        if divisor == 0.0 {
+                            return Err("Division by zero".into());
+                        }
+                        value /= divisor;
+                    }
+                    _ => return Ok(value),
+                }
+            }
+        }
+
+        fn factor(&mut self) -> Result<f64, String> {
+            self.depth += 1;
+            if self.depth > MAX_EVAL_DEPTH {
+                return Err("Expression is nested too deeply".into());
+            }
+            let value = self.factor_inner();
+            self.depth -= 1;
+            value
+        }
+
+        fn factor_inner(&mut self) -> Result<f64, String> {
+            match self.peek() {
+                Some('-') => {
+                    self.chars.next();
+                    Ok(-self.factor()?)
+                }
+                Some('(') => {
+                    self.chars.next();
+                    let value = self.expr()?;
+                    match self.peek() {
+                        Some(')') => {
+                            self.chars.next();
+                            Ok(value)
+                        }
+                        _ => Err("Missing closing parenthesis".into()),
+                    }
+                }
+                Some(c) if c.is_ascii_digit() || c == '.' => {
+                    let (start, _) = *self.chars.peek().expect("peeked");
+                    let mut end = start;
+                    while let Some((i, c)) =
+                        self.chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.')
+                    {
+                        end = i + c.len_utf8();
+                    }
+                    self.text[start..end]
+                        .parse()
+                        .map_err(|_| format!("Invalid number {}", &self.text[start..end]))
+                }
+                Some(c) => Err(format!("Unexpected {c}")),
+                None => Err("Unexpected end of expression".into()),
+            }
+        }
+    }
+
+    let mut parser = Parser {
+        chars: text.char_indices().peekable(),
+        text,
+        depth: 0,
+    };
+    let value = parser.expr()?;
+    match parser.peek() {
+        Some(c) => Err(format!("Unexpected {c}")),
+        None => Ok(value),
+    }
+}
+
+/// Bounds width and precision, which would otherwise let a spec allocate arbitrarily much
+const MAX_PRINTF_WIDTH: usize = 1_000;
+
+fn printf(spec: &str, text: &str) -> Data {
+    let mut formatted = String::new();
+    let mut chars = spec.chars().peekable();
+    let mut directives = 0;
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            formatted.push(c);
+            continue;
+        }
+        if chars.next_if_eq(&'%').is_some() {
+            formatted.push('%');
+            continue;
+        }
+        directives += 1;
+        if directives > 1 {
+            return Data::Error("Only one % directive is supported".into());
+        }
+        let (mut left, mut plus, mut zero) = (false, false, false);
+        while let Some(flag) = chars.next_if(|c| matches!(c, '-' | '+' | '0')) {
+            match flag {
+                '-' => left = true,
+                '+' => plus = true,
+                _ => zero = true,
+            }
+        }
+        let number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+            let mut digits = String::new();
+            while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                digits.push(d);
+            }
+            // Too many digits for a usize is caught as too large below
+            (!digits.is_empty()).then(|| digits.parse::<usize>().unwrap_or(usize::MAX))
+        };
+        let width = number(&mut chars).unwrap_or(0);
+        let precision = chars
+            .next_if_eq(&'.')
+            .map(|_| number(&mut chars).unwrap_or(0));
+        if width > MAX_PRINTF_WIDTH {
+            return Data::Error("Width too large".into());
+        }
+        if precision.is_some_and(|precision| precision > MAX_PRINTF_WIDTH) {
+            return Data::Error("Precision too large".into());
+        }
+        let Some(conv) = chars.next() else {
+            return Data::Error("Missing conversion".into());
+        };
+
+        let text = text.trim();
+        let (negative, digits) = if conv == 'f' {
+            let Ok(value) = text.parse::<f64>() else {
+                return Data::Error("Not a number".into());
+            };
+            let precision = precision.unwrap_or(6);
+            (value < 0.0, format!("{:.precision$}", value.abs()))
+        } else {
+            let Ok(value) = text.parse::<i64>() else {
+                return Data::Error("Not a whole number".into());
+            };
+            let abs = value.unsigned_abs();
+            let digits = match conv {
+                'd' | 'i' => abs.to_string(),
+                'x' => format!("{abs:x}"),
+                'X' => format!("{abs:X}"),
+                'o' => format!("{abs:o}"),
+                'b' => format!("{abs:b}"),
+                _ => return Data::Error(format!("Unsupported conversion '{conv}'")),
+            };
+            (value < 0, digits)
+        };
+        let sign = match (negative, plus) {
+            (true, _) => "-",
+            (false, true) => "+",
+            (false, false) => "",
+        };
+        let padding = width.saturating_sub(sign.len() + digits.len());
+        if left {
+            formatted.push_str(&format!("{sign}{digits}{}", " ".repeat(padding)));
+        } else if zero {
+            formatted.push_str(&format!("{sign}{}{digits}", "0".repeat(padding)));
+        } else {
+            formatted.push_str(&format!("{}{sign}{digits}", " ".repeat(padding)));
+        }
+    }
+    if directives == 0 {
+        return Data::Error("Spec has no % directive".into());
+    }
+    Data::Text(formatted)
+}
+
+const DURATION_UNITS: [(char, u64); 4] = [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+/// Seconds in either a plain number or a `1d2h3m4s`-style duration
+fn parse_duration(text: &str) -> Option<u64> {
+    if let Ok(seconds) = text.parse() {
+        return Some(seconds);
+    }
+    if text.is_empty() {
+        return None;
+    }
+    let mut seconds = 0u64;
+    let mut number = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let (_, unit) = DURATION_UNITS.iter().find(|(u, _)| *u == c)?;
+        let count: u64 = std::mem::take(&mut number).parse().ok()?;
+        seconds = seconds.checked_add(count.checked_mul(*unit)?)?;
+    }
+    number.is_empty().then_some(seconds)
+}
+
+fn format_duration(mut seconds: u64) -> String {
+    if seconds == 0 {
+        return "0s".into();
+    }
+    let mut formatted = String::new();
+    for (unit, length) in DURATION_UNITS {
+        if seconds >= length {
+            formatted.push_str(&format!("{}{unit}", seconds / length));
+            seconds %= length;
+        }
+    }
+    formatted
+}
+
+fn markdown(items: &[Data], kind: &MdKind) -> Data {
+    fn cell(data: &Data) -> Option<String> {
+        match data {
+            Data::Text(text) => Some(text.replace('|', "\\|").replace('\n', " ")),
+            Data::Bytes(bytes) => cell(&Data::Text(String::from_utf8_lossy(bytes).into())),
+            Data::List(list) => Some(
+                list.iter()
+                    .map(cell)
+                    .collect::<Option<Vec<_>>>()?
+                    .join(", "),
+            ),
+            Data::Error(_) => None,
+        }
+    }
+    let mut lines = Vec::with_capacity(items.len());
+    if *kind == MdKind::Table {
+        let mut rows = Vec::with_capacity(items.len());
+        for item in items {
+            let Data::List(row) = item else {
+                return Data::Error("Table rows must be Lists".into());
+            };
+            match row.iter().map(cell).collect::<Option<Vec<_>>>() {
+                Some(row) => rows.push(row),
+                None => return Data::Error("Input error".into()),
+            }
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for (i, mut row) in rows.into_iter().enumerate() {
+            row.resize(width, String::new());
+            lines.push(format!("| {} |", row.join(" | ")));
+            if i == 0 {
+                lines.push(format!("|{}", " --- |".repeat(width)));
+            }
+        }
+    } else {
+        for (i, item) in items.iter().enumerate() {
+            let Some(item) = cell(item) else {
+                return Data::Error("Input error".into());
+            };
+            lines.push(match kind {
+                MdKind::Numbered => format!("{}. {item}", i + 1),
+                _ => format!("- {item}"),
+            });
+        }
+    }
+    Data::Text(lines.join("\n"))
+}
+
+/// Compares texts chunk by chunk, runs of digits by their value and everything else as is
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let chunks = |text: &str| -> Vec<(bool, String)> {
+        let mut chunks: Vec<(bool, String)> = Vec::new();
+        for c in text.chars() {
+            let digit = c.is_ascii_digit();
+            match chunks.last_mut() {
+                Some((d, chunk)) if *d == digit => chunk.push(c),
+                _ => chunks.push((digit, c.to_string())),
+            }
+        }
+        chunks
+    };
+    for (a, b) in chunks(a).iter().zip(&chunks(b)) {
+        let ordering = match (a, b) {
+            ((true, a), (true, b)) => {
+                let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+                a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+            }
+            ((_, a), (_, b)) => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    chunks(a).len().cmp(&chunks(b).len())
+}
+
+/// Small deterministic PRNG step, so seeded results don't depend on any library's algorithm
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+const LOWERCASE: &[u8; 26] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Letters and the digits standing in for them in leetspeak
+const LEET: [(char, char); 9] = [
+    ('a', '4'),
+    ('b', '8'),
+    ('e', '3'),
+    ('g', '6'),
+    ('l', '1'),
+    ('o', '0'),
+    ('s', '5'),
+    ('t', '7'),
+    ('z', '2'),
+];
+
+/// Code point of 🇦, followed by the rest of the alphabet
+const REGIONAL_INDICATOR_A: u32 = 0x1F1E6;
+
+const TAP_CODE_GRID: &[u8; 25] = b"abcdefghijlmnopqrstuvwxyz";
+
+fn tap_code(text: &str, decode: bool) -> Data {
+    if !decode {
+        let mut words = Vec::new();
+        for word in text.split_whitespace() {
+            let mut letters = Vec::new();
+            for c in word.chars() {
+                let lower = match c.to_ascii_lowercase() {
+                    'k' => 'c',
+                    c => c,
+                };
+                let Some(i) = TAP_CODE_GRID.iter().position(|&l| l as char == lower) else {
+                    return Data::Error(format!("'{c}' has no tap code"));
+                };
+                letters.push(format!(
+                    "{} {}",
+                    ".".repeat(i / 5 + 1),
+                    ".".repeat(i % 5 + 1)
+                ));
+            }
+            words.push(letters.join("  "));
+        }
+        return Data::Text(words.join(" / "));
+    }
+    let mut words = Vec::new();
+    for word in text.split('/') {
+        let taps = word.split_whitespace().collect::<Vec<_>>();
+        if !taps.len().is_multiple_of(2) {
+            return Data::Error("Unpaired taps".into());
+        }
+        let mut letters = String::new();
+        for pair in taps.chunks(2) {
+            let (row, col) = (pair[0], pair[1]);
+            if !(row.chars().chain(col.chars())).all(|c| c == '.') || row.len() > 5 || col.len() > 5
+            {
+                return Data::Error(format!("Invalid taps '{row} {col}'"));
+            }
+            letters.push(TAP_CODE_GRID[(row.len() - 1) * 5 + col.len() - 1] as char);
+        }
+        words.push(letters);
+    }
+    Data::Text(words.join(" "))
+}
+
+fn columnar_transposition(key: &str, text: &str, decrypt: bool) -> Data {
+    let key: Vec<char> = key.chars().collect();
+    if key.is_empty() {
+        return Data::Error("Key can't be empty".into());
+    }
+    let mut order: Vec<usize> = (0..key.len()).collect();
+    order.sort_by_key(|&c| key[c]);
+
+    let text: Vec<char> = text.chars().collect();
+    let columns = key.len();
+    let cells = |column: usize| (column..text.len()).step_by(columns);
+    if !decrypt {
+        return Data::Text(
+            order
+                .iter()
+                .flat_map(|&c| cells(c))
+                .map(|i| text[i])
+                .collect(),
+        );
+    }
+    let mut plaintext = vec![' '; text.len()];
+    for (i, c) in order.iter().flat_map(|&c| cells(c)).zip(&text) {
+        plaintext[i] = *c;
+    }
+    Data::Text(plaintext.into_iter().collect())
+}
+
+fn playfair(key: &str, text: &str, decrypt: bool) -> Data {
+    let letters = |text: &str| -> Vec<u8> {
+        text.bytes()
+            .filter(u8::is_ascii_alphabetic)
+            .map(|b| match b.to_ascii_uppercase() {
+                b'J' => b'I',
+                b => b,
+            })
+            .collect()
+    };
+
+    let mut square = Vec::with_capacity(25);
+    for letter in letters(key).into_iter().chain(b'A'..=b'Z') {
+        if letter != b'J' && !square.contains(&letter) {
+            square.push(letter);
+        }
+    }
+    let find = |letter: u8| {
+        let i = square
+            .iter()
+            .position(|&l| l == letter)
+            .expect("every letter but J");
+        (i / 5, i % 5)
+    };
+
+    let mut letters = letters(text);
+    if !decrypt {
+        let mut prepared = Vec::with_capacity(letters.len() + 1);
+        for letter in letters {
+            if prepared.len() % 2 == 1 && prepared.last() == Some(&letter) {
+                prepared.push(if letter == b'X' { b'Q' } else { b'X' });
+            }
+            prepared.push(letter);
+        }
+        if prepared.len() % 2 == 1 {
+            prepared.push(if prepared.last() == Some(&b'X') {
+                b'Q'
+            } else {
+                b'X'
+            });
+        }
+        letters = prepared;
+    } else if letters.len() % 2 == 1 {
+        return Data::Error("Odd number of letters".into());
+    }
+
+    let shift = if decrypt { 4 } else { 1 };
+    let mut result = String::with_capacity(letters.len());
+    for pair in letters.chunks(2) {
+        let ((r1, c1), (r2, c2)) = (find(pair[0]), find(pair[1]));
+        let (a, b) = if r1 == r2 {
+            ((r1, (c1 + shift) % 5), (r2, (c2 + shift) % 5))
+        } else if c1 == c2 {
+            (((r1 + shift) % 5, c1), ((r2 + shift) % 5, c2))
+        } else {
+            ((r1, c2), (r2, c1))
+        };
+        result.push(square[a.0 * 5 + a.1] as char);
+        result.push(square[b.0 * 5 + b.1] as char);
+    }
+    Data::Text(result)
+}
+
+const MAX_RUN_LENGTH_OUTPUT: usize = 10_000_000;
+
+/// Parses exactly two hex digits
+fn hex_byte(hex: &[u8]) -> Option<u8> {
+    match hex {
+        [hi, lo] if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {
+            u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Builds a Base64 engine from 64 alphabet characters and an optional padding character
+fn custom_base64(alphabet: &str) -> Result<(GeneralPurpose, char), String> {
+    let (alphabet, padding) = match alphabet.char_indices().nth(64) {
+        Some((i, padding)) if alphabet.len() == i + padding.len_utf8() => (&alphabet[..i], padding),
+        _ => (alphabet, '='),
+    };
+    let alphabet = Alphabet::new(alphabet).map_err(|err| format!("Invalid alphabet: {err}"))?;
+    let config =
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+    Ok((GeneralPurpose::new(&alphabet, config), padding))
+}
+
+/// Runs of at least `min_len` printable ASCII characters, like Unix `strings`
+fn strings(bytes: &[u8], min_len: usize) -> Data {
+    Data::List(
+        bytes
+            .split(|b| !(b.is_ascii_graphic() || *b == b' ' || *b == b'\t'))
+            .filter(|run| run.len() >= min_len.max(1))
+            .map(|run| Data::Text(String::from_utf8_lossy(run).into()))
+            .collect(),
+    )
+}
+
+/// Format, width and height of a PNG, JPEG or GIF image
+fn image_info(bytes: &[u8]) -> Data {
+    let reader = match ImageReader::new(Cursor::new(bytes)).with_guessed_format() {
+        Ok(reader) => reader,
+        Err(err) => return Data::Error(err.to_string()),
+    };
+    let format = match reader.format() {
+        Some(ImageFormat::Png) => "PNG",
+        Some(ImageFormat::Jpeg) => "JPEG",
+        Some(ImageFormat::Gif) => "GIF",
+        _ => return Data::Error("Not a PNG, JPEG or GIF image".into()),
+    };
+    match reader.into_dimensions() {
+        Ok((width, height)) => Data::List(vec![
+            Data::Text(format!("format={format}")),
+            Data::Text(format!("width={width}")),
+            Data::Text(format!("height={height}")),
+        ]),
+        Err(err) => Data::Error(err.to_string()),
+    }
+}
+
+/// Reads binary data written as hex or Base64
+fn ciphertext_bytes(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim();
+    if !text.is_empty()
+        && text.len().is_multiple_of(2)
+        && text.bytes().all(|b| b.is_ascii_hexdigit())
+    {
+        Some(text.as_bytes().chunks(2).filter_map(hex_byte).collect())
+    } else {
+        BASE64_STANDARD.decode(text).ok()
+    }
+}
+
+const SINGLE_BYTE_XOR_RESULTS: usize = 5;
+
+fn single_byte_xor(bytes: &[u8]) -> Data {
+    // Relative frequencies of letters in English text, in percent
+    const FREQUENCIES: [f64; 26] = [
+        8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095,
+        6.0, 6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+    ];
+    let score = |plaintext: &[u8]| -> f64 {
+        plaintext
+            .iter()
+            .map(|b| match b.to_ascii_lowercase() {
+                c @ b'a'..=b'z' => FREQUENCIES[(c - b'a') as usize],
+                b' ' => 13.0,
+                b'\n' | b'\r' | b'\t' => 0.0,
+                c if c.is_ascii_graphic() => 0.0,
+                _ => -20.0,
+            })
+            .sum()
+    };
+    let mut candidates: Vec<(u8, Vec<u8>, f64)> = (0..=u8::MAX)
+        .map(|key| {
+            let plaintext: Vec<u8> = bytes.iter().map(|b| b ^ key).collect();
+            let score = score(&plaintext);
+            (key, plaintext, score)
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.2.total_cmp(&a.2));
+    Data::List(
+        candidates
+            .into_iter()
+            .take(SINGLE_BYTE_XOR_RESULTS)
+            .map(|(key, plaintext, _)| {
+                Data::Text(format!(
+                    "{key:02x}: {}",
+                    String::from_utf8_lossy(&plaintext)
+                ))
+            })
+            .collect(),
+    )
+}
+
+fn guess_xor_key_len(bytes: &[u8], max: usize) -> Data {
+    let hamming =
+        |a: &[u8], b: &[u8]| -> u32 { a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum() };
+    let mut scores: Vec<(usize, f64)> = (1..=max)
+        .filter(|len| bytes.len() >= len * 2)
+        .map(|len| {
+            let blocks: Vec<&[u8]> = bytes.chunks_exact(len).collect();
+            let total: u32 = blocks.windows(2).map(|w| hamming(w[0], w[1])).sum();
+            let score = total as f64 / (blocks.len() - 1) as f64 / len as f64;
+            (len, score)
+        })
+        .collect();
+    if scores.is_empty() {
+        return Data::Error("Not enough data".into());
+    }
+    scores.sort_by(|a, b| a.1.total_cmp(&b.1));
+    Data::List(
+        scores
+            .into_iter()
+            .map(|(len, _)| Data::Text(len.to_string()))
+            .collect(),
+    )
+}
+
+const MAX_RANGE_LEN: u64 = 100_000;
+
+fn range(start: i64, end: i64, step: i64) -> Data {
+    if step == 0 {
+        return Data::Error("Step can't be zero".into());
+    }
+    let span = end.abs_diff(start);
+    if (end > start && step < 0) || (end < start && step > 0) {
+        return Data::List(Vec::new());
+    }
+    if span / step.unsigned_abs() >= MAX_RANGE_LEN {
+        return Data::Error("Range is too large".into());
+    }
+    let len = span / step.unsigned_abs() + 1;
+    Data::List(
+        // Widened since `i * step` can overflow i64 even when the value it lands on fits
+        (0..len as i128)
+            .map(|i| Data::Text((i128::from(start) + i * i128::from(step)).to_string()))
+            .collect(),
+    )
+}
+
+fn gcd_lcm(numbers: &[Data], lcm: bool) -> Data {
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+    let mut result = if lcm { 1 } else { 0 };
+    for number in numbers {
+        let number = match number {
+            Data::Text(text) => match text.trim().parse::<u64>() {
+                Ok(number) => number,
+                Err(_) => return Data::Error(format!("{text} is not a whole number")),
+            },
+            _ => return Data::Error("Input error".into()),
+        };
+        result = if !lcm {
+            gcd(result, number)
+        } else if number == 0 || result == 0 {
+            0
+        } else {
+            match (result / gcd(result, number)).checked_mul(number) {
+                Some(lcm) => lcm,
+                None => return Data::Error("LCM is too large".into()),
+            }
+        };
+    }
+    Data::Text(result.to_string())
+}
+
+/// Trial division gives up past this, which bounds the work to well under a second
+const MAX_TRIAL_DIVISOR: u64 = 10_000_000;
+
+fn factorize(mut number: u64) -> Data {
+    if number == 0 {
+        return Data::Error("0 has no prime factors".into());
+    }
+    let mut factors = Vec::new();
+    let mut divisor = 2;
+    while divisor * divisor <= number {
+        if divisor > MAX_TRIAL_DIVISOR {
+            return Data::Error("Too large to factor".into());
+        }
+        while number.is_multiple_of(divisor) {
+            factors.push(Data::Text(divisor.to_string()));
+            number /= divisor;
+        }
+        divisor += 1;
+    }
+    if number > 1 {
+        factors.push(Data::Text(number.to_string()));
+    }
+    Data::List(factors)
+}
+
+const MAX_PERMUTATIONS: usize = 100_000;
+
+/// Returns `None` if there would be more than [`MAX_PERMUTATIONS`]
+fn permutations<T: Clone>(items: &[T], k: usize) -> Option<Vec<Vec<T>>> {
+    if k > items.len() {
+        return Some(Vec::new());
+    }
+    let mut count: usize = 1;
+    for i in 0..k {
+        count = count.saturating_mul(items.len() - i);
+    }
+    if count > MAX_PERMUTATIONS {
+        return None;
+    }
+
+    fn permute<T: Clone>(
+        items: &[T],
+        k: usize,
+        used: &mut Vec<bool>,
+        current: &mut Vec<T>,
+        out: &mut Vec<Vec<T>>,
+    ) {
+        if current.len() == k {
+            out.push(current.clone());
+            return;
+        }
+        for i in 0..items.len() {
+            if !used[i] {
+                used[i] = true;
+                current.push(items[i].clone());
+                permute(items, k, used, current, out);
+                current.pop();
+                used[i] = false;
+            }
+        }
+    }
+    let mut out = Vec::with_capacity(count);
+    permute(
+        items,
+        k,
+        &mut vec![false; items.len()],
+        &mut Vec::new(),
+        &mut out,
+    );
+    Some(out)
+}
+
+fn detect_charset(bytes: &[u8]) -> Data {
+    let mut detector = EncodingDetector::new(Iso2022JpDetection::Allow);
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, Utf8Detection::Allow);
+    let (decoded, _, _) = encoding.decode(bytes);
+    Data::List(vec![
+        Data::Text(encoding.name().into()),
+        Data::Text(decoded.into()),
+    ])
+}
+
+fn group_thousands(number: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    let (int, frac) = match unsigned.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (unsigned, None),
+    };
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    match frac {
+        Some(frac) => format!("{sign}{grouped}.{frac}"),
+        None => format!("{sign}{grouped}"),
+    }
+}
+
+fn decode_html_entities(text: &str) -> String {
+    let entity = Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);").expect("valid regex");
+    entity
+        .replace_all(text, |caps: &regex::Captures| {
+            let name = &caps[1];
+            let decoded = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => name
+                    .strip_prefix("#x")
+                    .or_else(|| name.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| name.strip_prefix('#').map(|dec| dec.parse()))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            decoded.map_or_else(|| caps[0].to_string(), String::from)
+        })
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let transformer = Transformer::Split {
+            pattern: " ".into(),
+        };
+
+        test_transformer(
+            &transformer,
+            Data::Text("Sample Text".into()),
+            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![
+                Data::Text("Sample Text".into()),
+                Data::Text("Another Sample Text".into()),
+            ]),
+            Data::List(vec![
+                Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+                Data::List(vec![
+                    Data::Text("Another".into()),
+                    Data::Text("Sample".into()),
+                    Data::Text("Text".into()),
+                ]),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        let transformer = Transformer::Join {
+            separator: " ".into(),
+            prefix: String::new(),
+            suffix: String::new(),
+        };
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+            Data::Text("Sample Text".into()),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![
+                Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+                Data::List(vec![
+                    Data::Text("Another".into()),
+                    Data::Text("Sample".into()),
+                    Data::Text("Text".into()),
+                ]),
+            ]),
+            Data::Text("Sample Text Another Sample Text".into()),
+        );
+
+        test_transformer(
+            &Transformer::Join {
+                separator: ", ".into(),
+                prefix: "\"".into(),
+                suffix: "\"".into(),
+            },
+            Data::List(vec![Data::Text("a".into()), Data::Text("b".into())]),
+            Data::Text("\"a\", \"b\"".into()),
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        let transformer = Transformer::Find {
+            pattern: "Text".into(),
+            flatten: false,
+        };
+
+        test_transformer(
+            &transformer,
+            Data::Text("Sample Text".into()),
+            Data::List(vec![Data::Text("Text".into())]),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![
+                Data::Text("Sample Text".into()),
+                Data::Text("Another Sample Text".into()),
+            ]),
+            Data::List(vec![
+                Data::List(vec![Data::Text("Text".into())]),
+                Data::List(vec![Data::Text("Text".into())]),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_find_flatten() {
+        let transformer = Transformer::Find {
+            pattern: "[A-Z]".into(),
+            flatten: true,
+        };
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![
+                Data::Text("Sample Text".into()),
+                Data::Text("Another Sample".into()),
+            ]),
+            Data::List(vec![
+                Data::Text("0: S".into()),
+                Data::Text("0: T".into()),
+                Data::Text("1: A".into()),
+                Data::Text("1: S".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_replace() {
+        let transformer = Transformer::Replace {
+            pattern: "Sample".into(),
+            replacer: "Test".into(),
+        };
+
+        test_transformer(
+            &transformer,
+            Data::Text("Sample Text".into()),
+            Data::Text("Test Text".into()),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![
+                Data::Text("Sample Text".into()),
+                Data::Text("Another Sample Text".into()),
+            ]),
+            Data::List(vec![
+                Data::Text("Test Text".into()),
+                Data::Text("Another Test Text".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_slice() {
+        let transformer = Transformer::Slice { from: 1, to: 6 };
+
+        test_transformer(
+            &transformer,
+            Data::Text("Sample Text".into()),
+            Data::Text("ample".into()),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![
+                Data::Text("Sample Text".into()),
+                Data::Text("Another Sample Text".into()),
+            ]),
+            Data::List(vec![Data::Text("ample".into()), Data::Text("nothe".into())]),
+        );
+    }
+
+    // TODO: encode and decode tests
+
+    #[test]
+    fn test_base58() {
+        let encode = Transformer::Encode {
+            encoding: Encoding::Base58,
+        };
+        let decode = Transformer::Decode {
+            encoding: Encoding::Base58,
+        };
+
+        test_transformer(
+            &encode,
+            Data::Text("Hello World!".into()),
+            Data::Text("2NEpo7TZRRrLZSi2U".into()),
+        );
+
+        test_transformer(
+            &decode,
+            encode.transform(&Data::Text("Sample Text".into())),
+            Data::Text("Sample Text".into()),
+        );
+
+        assert!(matches!(
+            decode.transform(&Data::Text("0OIl".into())),
+            Data::Error(_)
+        ));
+    }
+
+    #[test]
+    fn test_uppercase() {
+        let transformer = Transformer::Uppercase;
+
+        test_transformer(
+            &transformer,
+            Data::Text("Sample Text".into()),
+            Data::Text("SAMPLE TEXT".into()),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+            Data::List(vec![Data::Text("SAMPLE".into()), Data::Text("TEXT".into())]),
+        );
+    }
+
+    #[test]
+    fn test_lowercase() {
+        let transformer = Transformer::Lowercase;
+
+        test_transformer(
+            &transformer,
+            Data::Text("Sample Text".into()),
+            Data::Text("sample text".into()),
+        );
+
+        test_transformer(
+            &transformer,
+            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+            Data::List(vec![Data::Text("sample".into()), Data::Text("text".into())]),
+        );
+    }
+
+    #[test]
+    fn test_checksum() {
+        test_transformer(
+            &Transformer::Checksum {
+                algo: ChecksumAlgo::Crc32,
+            },
+            Data::Text("The quick brown fox jumps over the lazy dog".into()),
+            Data::Text("414fa339".into()),
+        );
+
+        test_transformer(
+            &Transformer::Checksum {
+                algo: ChecksumAlgo::Adler32,
+            },
+            Data::Text("The quick brown fox jumps over the lazy dog".into()),
+            Data::Text("5bdc0fda".into()),
+        );
+    }
+
+    #[test]
+    fn test_form_decode() {
+        test_transformer(
+            &Transformer::FormDecode,
+            Data::Text("name=John+Doe&age=30".into()),
+            Data::List(vec![
+                Data::Text("name=John Doe".into()),
+                Data::Text("age=30".into()),
+            ]),
+        );
+
+        test_transformer(
+            &Transformer::FormDecode,
+            Data::Text("q=a%26b&flag".into()),
+            Data::List(vec![Data::Text("q=a&b".into()), Data::Text("flag=".into())]),
+        );
+    }
+
+    #[test]
+    fn test_strip_html() {
+        test_transformer(
+            &Transformer::StripHtml {
+                decode_entities: false,
+            },
+            Data::Text("<p>Hello <b>World</b></p>".into()),
+            Data::Text("Hello World".into()),
+        );
+
+        test_transformer(
+            &Transformer::StripHtml {
+                decode_entities: true,
+            },
+            Data::Text("<p>Fish &amp; Chips &#65;&#x42;</p><script>alert(1)</script>".into()),
+            Data::Text("Fish & Chips AB".into()),
+        );
+    }
+
+    #[test]
+    fn test_number_format() {
+        test_transformer(
+            &Transformer::NumberFormat {
+                thousands: true,
+                decimals: Some(2),
+            },
+            Data::Text("1234567.891".into()),
+            Data::Text("1,234,567.89".into()),
+        );
+
+        test_transformer(
+            &Transformer::NumberFormat {
+                thousands: true,
+                decimals: None,
+            },
+            Data::Text("-1000".into()),
+            Data::Text("-1,000".into()),
+        );
+
+        test_transformer(
+            &Transformer::NumberFormat {
+                thousands: false,
+                decimals: None,
+            },
+            Data::Text("abc".into()),
+            Data::Error("Not a number".into()),
+        );
+    }
+
+    #[test]
+    fn test_multi_replace() {
+        test_transformer(
+            &Transformer::MultiReplace {
+                rules: vec![
+                    ("Sample".into(), "Test".into()),
+                    ("Test Text".into(), "Done".into()),
+                ],
+            },
+            Data::Text("Sample Text".into()),
+            Data::Text("Done".into()),
+        );
+
+        test_transformer(
+            &Transformer::MultiReplace {
+                rules: vec![("a".into(), "b".into()), ("(".into(), "c".into())],
+            },
+            Data::Text("Sample Text".into()),
+            Data::Error("Invalid pattern in rule 2".into()),
+        );
+    }
+
+    #[test]
+    fn test_sort_chars() {
+        test_transformer(
+            &Transformer::SortChars { descending: false },
+            Data::Text("dcba".into()),
+            Data::Text("abcd".into()),
+        );
+
+        test_transformer(
+            &Transformer::SortChars { descending: true },
+            Data::List(vec![
+                Data::Text("listen".into()),
+                Data::Text("silent".into()),
+            ]),
+            Data::List(vec![
+                Data::Text("tsnlie".into()),
+                Data::Text("tsnlie".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_unix_time() {
+        test_transformer(
+            &Transformer::UnixTime { to_date: true },
+            Data::Text("1234567890".into()),
+            Data::Text("2009-02-13T23:31:30Z".into()),
+        );
+
+        test_transformer(
+            &Transformer::UnixTime { to_date: false },
+            Data::Text("2009-02-13T23:31:30Z".into()),
+            Data::Text("1234567890".into()),
+        );
+
+        test_transformer(
+            &Transformer::UnixTime { to_date: false },
+            Data::Text("2009-02-14T00:31:30+01:00".into()),
+            Data::Text("1234567890".into()),
+        );
+    }
+
+    #[test]
+    fn test_swap_endian() {
+        test_transformer(
+            &Transformer::SwapEndian { word_size: 4 },
+            Data::Text("12345678".into()),
+            Data::Text("78563412".into()),
+        );
+
+        test_transformer(
+            &Transformer::SwapEndian { word_size: 2 },
+            Data::Text("1234 abcd".into()),
+            Data::Text("3412cdab".into()),
+        );
+
+        test_transformer(
+            &Transformer::SwapEndian { word_size: 4 },
+            Data::Text("123456".into()),
+            Data::Error("Length is not a multiple of the word size".into()),
+        );
+    }
+
+    #[test]
+    fn test_bacon() {
+        test_transformer(
+            &Transformer::Bacon { decode: false },
+            Data::Text("hi!".into()),
+            Data::Text("AABBB ABAAA".into()),
+        );
+
+        test_transformer(
+            &Transformer::Bacon { decode: true },
+            Data::Text("AABBB ABAAA".into()),
+            Data::Text("HI".into()),
+        );
+
+        test_transformer(
+            &Transformer::Bacon { decode: true },
+            Data::Text("BBBBB".into()),
+            Data::Error("Invalid Bacon group".into()),
+        );
+    }
+
+    #[test]
+    fn test_extract_entities() {
+        test_transformer(
+            &Transformer::ExtractEntities {
+                kind: EntityKind::Email,
+            },
+            Data::Text(
+                "Mail alice@example.com or bob.smith+puzzles@mail.example.org today.".into(),
+            ),
+            Data::List(vec![
+                Data::Text("alice@example.com".into()),
+                Data::Text("bob.smith+puzzles@mail.example.org".into()),
+            ]),
+        );
+
+        test_transformer(
+            &Transformer::ExtractEntities {
+                kind: EntityKind::Url,
+            },
+            Data::Text("See https://example.com/a?b=c, then http://test.org.".into()),
+            Data::List(vec![
+                Data::Text("https://example.com/a?b=c".into()),
+                Data::Text("http://test.org".into()),
+            ]),
+        );
+
+        test_transformer(
+            &Transformer::ExtractEntities {
+                kind: EntityKind::Ipv4,
+            },
+            Data::Text("from 10.0.0.1 to 256.1.1.1 via 192.168.0.254".into()),
+            Data::List(vec![
+                Data::Text("10.0.0.1".into()),
+                Data::Text("192.168.0.254".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_rotations() {
+        test_transformer(
+            &Transformer::Rotations,
+            Data::Text("abc".into()),
+            Data::List(vec![
+                Data::Text("abc".into()),
+                Data::Text("bca".into()),
+                Data::Text("cab".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_not() {
+        test_transformer(
+            &Transformer::Not,
+            Data::Text("ab".into()),
+            Data::Bytes(vec![0x9e, 0x9d]),
+        );
+
+        let input = Data::Text("Sample Text".into());
+        assert_eq!(
+            Transformer::Not.transform(&Transformer::Not.transform(&input)),
+            input
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let bytes = Data::Bytes(vec![0xde, 0xad, 0xbe, 0xef]);
+
+        test_transformer(
+            &Transformer::Encode {
+                encoding: Encoding::Base64,
+            },
+            bytes.clone(),
+            Data::Text("3q2+7w==".into()),
+        );
+
+        test_transformer(
+            &Transformer::Decode {
+                encoding: Encoding::Base64,
+            },
+            Data::Text("3q2+7w==".into()),
+            bytes,
+        );
+
+        test_transformer(
+            &Transformer::Uppercase,
+            Data::Bytes(b"abc".to_vec()),
+            Data::Text("ABC".into()),
+        );
+    }
+
+    #[test]
+    fn test_detect_charset() {
+        // "Très intéressant, ça coûte à peine un café." in Windows-1252
+        let mut bytes = b"Tr\xe8s int\xe9ressant, \xe7a co\xfbte \xe0 peine un caf\xe9.".to_vec();
+        bytes.extend_from_slice(b" Voil\xe0 d\xe9j\xe0 une id\xe9e tr\xe8s fran\xe7aise.");
+
+        test_transformer(
+            &Transformer::DetectCharset,
+            Data::Bytes(bytes),
+            Data::List(vec![
+                Data::Text("windows-1252".into()),
+                Data::Text(
+                    "Très intéressant, ça coûte à peine un café. Voilà déjà une idée très française."
+                        .into(),
+                ),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_transcode() {
+        test_transformer(
+            &Transformer::Transcode {
+                from: CharsetName::ShiftJis,
+            },
+            Data::Bytes(vec![
+                0x82, 0xb1, 0x82, 0xf1, 0x82, 0xc9, 0x82, 0xbf, 0x82, 0xcd,
+            ]),
+            Data::Text("こんにちは".into()),
+        );
+
+        test_transformer(
+            &Transformer::Transcode {
+                from: CharsetName::Windows1252,
+            },
+            Data::Bytes(b"caf\xe9".to_vec()),
+            Data::Text("café".into()),
+        );
+    }
+
+    #[test]
+    fn test_regex_escape() {
+        test_transformer(
+            &Transformer::RegexEscape,
+            Data::Text(".*".into()),
+            Data::Text(r"\.\*".into()),
+        );
+    }
+
+    #[test]
+    fn test_permutations() {
+        let list =
+            |items: &[&str]| Data::List(items.iter().map(|i| Data::Text(i.to_string())).collect());
+        test_transformer(
+            &Transformer::Permutations { k: None },
+            list(&["a", "b", "c"]),
+            Data::List(vec![
+                list(&["a", "b", "c"]),
+                list(&["a", "c", "b"]),
+                list(&["b", "a", "c"]),
+                list(&["b", "c", "a"]),
+                list(&["c", "a", "b"]),
+                list(&["c", "b", "a"]),
+            ]),
+        );
+        test_transformer(
+            &Transformer::Permutations { k: Some(2) },
+            Data::Text("ab".into()),
+            list(&["ab", "ba"]),
+        );
+        test_transformer(
+            &Transformer::Permutations { k: None },
+            Data::Text("abcdefghijkl".into()),
+            Data::Error("Too many permutations".into()),
+        );
+    }
+
+    #[test]
+    fn test_math() {
+        test_transformer(
+            &Transformer::Math {
+                func: MathFunc::Sqrt,
+            },
+            Data::Text("16".into()),
+            Data::Text("4".into()),
+        );
+        test_transformer(
+            &Transformer::Math {
+                func: MathFunc::Abs,
+            },
+            Data::Text("-3".into()),
+            Data::Text("3".into()),
+        );
+        test_transformer(
+            &Transformer::Math {
+                func: MathFunc::ToDegrees,
+            },
+            Data::Text("3.141592653589793".into()),
+            Data::Text("180".into()),
+        );
+        test_transformer(
+            &Transformer::Math { func: MathFunc::Ln },
+            Data::Text("0".into()),
+            Data::Error("ln is undefined for 0".into()),
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        test_transformer(
+            &Transformer::Range {
+                start: i64::MIN,
+                end: i64::MAX,
+                step: i64::MAX,
+            },
+            Data::Text(String::new()),
+            Data::List(vec![
+                Data::Text(i64::MIN.to_string()),
+                Data::Text("-1".into()),
+                Data::Text((i64::MAX - 1).to_string()),
+            ]),
+        );
+        let transformer = Transformer::Range {
+            start: 1,
+            end: 5,
+            step: 2,
+        };
+        test_transformer(
+            &transformer,
+            Data::Text(String::new()),
+            Data::List(vec![
+                Data::Text("1".into()),
+                Data::Text("3".into()),
+                Data::Text("5".into()),
+            ]),
+        );
+        test_transformer(
+            &Transformer::Range {
+                start: 3,
+                end: 1,
+                step: -1,
+            },
+            Data::Text(String::new()),
+            Data::List(vec![
+                Data::Text("3".into()),
+                Data::Text("2".into()),
+                Data::Text("1".into()),
+            ]),
+        );
+        test_transformer(
+            &Transformer::Range {
+                start: 1,
+                end: 5,
+                step: 0,
+            },
+            Data::Text(String::new()),
+            Data::Error("Step can't be zero".into()),
+        );
+        test_transformer(
+            &Transformer::Range {
+                start: 0,
+                end: i64::MAX,
+                step: 1,
+            },
+            Data::Text(String::new()),
+            Data::Error("Range is too large".into()),
+        );
+    }
+
+    #[test]
+    fn test_caesar() {
+        test_transformer(
+            &Transformer::Caesar {
+                shift: 1,
+                digits: true,
+            },
+            Data::Text("ab12".into()),
+            Data::Text("bc23".into()),
+        );
+        test_transformer(
+            &Transformer::Caesar {
+                shift: -1,
+                digits: false,
+            },
+            Data::Text("Az 09".into()),
+            Data::Text("Zy 09".into()),
+        );
+    }
+
+    #[test]
+    fn test_hmac() {
+        test_transformer(
+            &Transformer::Hmac {
+                algo: HashAlgorithm::Sha256,
+                key: "Jefe".into(),
+            },
+            Data::Text("what do ya want for nothing?".into()),
+            Data::Text("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843".into()),
+        );
+    }
+
+    #[test]
+    fn test_config_convert() {
+        let json = "{\n  \"name\": \"puzzle\",\n  \"tags\": [\n    \"a\",\n    \"b\"\n  ]\n}";
+        let yaml = "name: puzzle\ntags:\n- a\n- b\n";
+        test_transformer(
+            &Transformer::ConfigConvert {
+                from: ConfigFmt::Json,
+                to: ConfigFmt::Yaml,
+            },
+            Data::Text(json.into()),
+            Data::Text(yaml.into()),
+        );
+        test_transformer(
+            &Transformer::ConfigConvert {
+                from: ConfigFmt::Yaml,
+                to: ConfigFmt::Json,
+            },
+            Data::Text(yaml.into()),
+            Data::Text(json.into()),
+        );
+        test_transformer(
+            &Transformer::ConfigConvert {
+                from: ConfigFmt::Json,
+                to: ConfigFmt::Toml,
+            },
+            Data::Text("{\"a\": 1}".into()),
+            Data::Text("a = 1\n".into()),
+        );
+    }
+
+    #[test]
+    fn test_partial_url_decode() {
+        test_transformer(
+            &Transformer::PartialUrlDecode {
+                escapes: "%20".into(),
+            },
+            Data::Text("a%20b%2Fc%2".into()),
+            Data::Text("a b%2Fc%2".into()),
+        );
+        test_transformer(
+            &Transformer::PartialUrlDecode {
+                escapes: "%2G".into(),
+            },
+            Data::Text("a%20b".into()),
+            Data::Error("Invalid escape %2G".into()),
+        );
+    }
+
+    #[test]
+    fn test_guess_xor_key_len() {
+        let plaintext = "Burning 'em, if you ain't quick and nimble\n\
+            I go crazy when I hear a cymbal\n\
+            and a high hat with a souped up tempo\n\
+            I'm on a roll, it's time to go solo";
+        let ciphertext: Vec<u8> = plaintext
+            .bytes()
+            .zip(b"ICE".iter().cycle())
+            .map(|(b, k)| b ^ k)
+            .collect();
+        let Data::List(candidates) =
+            Transformer::GuessXorKeyLen { max: 10 }.transform(&Data::Bytes(ciphertext))
+        else {
+            panic!("Expected a list");
+        };
+        assert_eq!(candidates[0], Data::Text("3".into()));
+    }
+
+    #[test]
+    fn test_single_byte_xor() {
+        let Data::List(candidates) = Transformer::SingleByteXor.transform(&Data::Text(
+            "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736".into(),
+        )) else {
+            panic!("Expected a list");
+        };
+        assert_eq!(
+            candidates[0],
+            Data::Text("58: Cooking MC's like a pound of bacon".into())
+        );
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        test_transformer(
+            &Transformer::CollapseWhitespace,
+            Data::Text("  a\t b\n c  ".into()),
+            Data::Text("a b c".into()),
+        );
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        test_transformer(
+            &Transformer::FixedWidth {
+                widths: vec![2, 3, 2],
+            },
+            Data::Text("AABBBCC".into()),
+            Data::List(vec![
+                Data::Text("AA".into()),
+                Data::Text("BBB".into()),
+                Data::Text("CC".into()),
+            ]),
+        );
+        test_transformer(
+            &Transformer::FixedWidth { widths: vec![1, 5] },
+            Data::Text("äöüß".into()),
+            Data::List(vec![Data::Text("ä".into()), Data::Text("öüß".into())]),
+        );
+    }
+
+    #[test]
+    fn test_sequence() {
+        test_transformer(
+            &Transformer::Sequence {
+                kind: SeqKind::Fibonacci,
+                count: 6,
+            },
+            Data::Text(String::new()),
+            Data::List(
+                ["0", "1", "1", "2", "3", "5"]
+                    .map(|n| Data::Text(n.into()))
+                    .to_vec(),
+            ),
+        );
+        test_transformer(
+            &Transformer::Sequence {
+                kind: SeqKind::Primes,
+                count: 5,
+            },
+            Data::Text(String::new()),
+            Data::List(
+                ["2", "3", "5", "7", "11"]
+                    .map(|n| Data::Text(n.into()))
+                    .to_vec(),
+            ),
+        );
+        test_transformer(
+            &Transformer::Sequence {
+                kind: SeqKind::Fibonacci,
+                count: 500,
+            },
+            Data::Text(String::new()),
+            Data::Error("Fibonacci numbers overflow after 187 terms".into()),
+        );
+    }
+
+    #[test]
+    fn test_base64_custom() {
+        let alphabet = "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba9876543210+/.";
+        test_transformer(
+            &Transformer::Base64Custom {
+                alphabet: alphabet.into(),
+                decode: false,
+            },
+            Data::Text("Sample Text".into()),
+            Data::Text("F7UgxTcoRUIovSJ.".into()),
+        );
+        test_transformer(
+            &Transformer::Base64Custom {
+                alphabet: alphabet.into(),
+                decode: true,
+            },
+            Data::Text("F7UgxTcoRUIovSJ.".into()),
+            Data::Text("Sample Text".into()),
+        );
+        test_transformer(
+            &Transformer::Base64Custom {
+                alphabet: "AABC".into(),
+                decode: true,
+            },
+            Data::Text(String::new()),
+            Data::Error("Invalid alphabet: Invalid length - must be 64 bytes".into()),
+        );
+    }
+
+    #[test]
+    fn test_luhn() {
+        test_transformer(
+            &Transformer::Luhn { verify: true },
+            Data::Text("4539 1488 0343 6467".into()),
+            Data::Text("true".into()),
+        );
+        test_transformer(
+            &Transformer::Luhn { verify: true },
+            Data::Text("4539 1488 0343 6468".into()),
+            Data::Text("false".into()),
+        );
+        test_transformer(
+            &Transformer::Luhn { verify: false },
+            Data::Text("7992739871".into()),
+            Data::Text("3".into()),
+        );
+        test_transformer(
+            &Transformer::Luhn { verify: false },
+            Data::Text("12a".into()),
+            Data::Error("Not a number".into()),
+        );
+    }
+
+    #[test]
+    fn test_decode_binary_format() {
+        let transformer = Transformer::DecodeBinaryFormat {
+            fmt: BinFmt::MessagePack,
+        };
+        test_transformer(
+            &transformer,
+            Data::Bytes(vec![
+                0x82, 0xa1, 0x61, 0x01, 0xa1, 0x62, 0x92, 0xc3, 0xa1, 0x78,
+            ]),
+            Data::Text("{\n  \"a\": 1,\n  \"b\": [\n    true,\n    \"x\"\n  ]\n}".into()),
+        );
+        test_transformer(
+            &transformer,
+            Data::Text("91".into()),
+            Data::Error("Invalid MessagePack data".into()),
+        );
+    }
+
+    #[test]
+    fn test_gray_code() {
+        test_transformer(
+            &Transformer::GrayCode { to_gray: true },
+            Data::Text("1011".into()),
+            Data::Text("1110".into()),
+        );
+        test_transformer(
+            &Transformer::GrayCode { to_gray: false },
+            Data::Text("1110".into()),
+            Data::Text("1011".into()),
+        );
+        test_transformer(
+            &Transformer::GrayCode { to_gray: true },
+            Data::Text("102".into()),
+            Data::Error("Not a binary string".into()),
+        );
+    }
+
+    #[test]
+    fn test_run_length() {
+        test_transformer(
+            &Transformer::RunLength { decode: false },
+            Data::Text("aaabbc".into()),
+            Data::Text("a3b2c1".into()),
+        );
+        test_transformer(
+            &Transformer::RunLength { decode: true },
+            Data::Text("a3b2c1".into()),
+            Data::Text("aaabbc".into()),
+        );
+        test_transformer(
+            &Transformer::RunLength { decode: true },
+            Data::Text("a3b".into()),
+            Data::Error("Invalid run length after b".into()),
+        );
+        test_transformer(
+            &Transformer::RunLength { decode: true },
+            Data::Text("a1b18446744073709551615".into()),
+            Data::Error("Output is too long".into()),
+        );
+        test_transformer(
+            &Transformer::RunLength { decode: true },
+            Data::Text(format!("é{}", MAX_RUN_LENGTH_OUTPUT / 2 + 1)),
+            Data::Error("Output is too long".into()),
+        );
+        test_transformer(
+            &Transformer::RunLength { decode: false },
+            Data::Text("a1".into()),
+            Data::Error("Can't run-length encode digits".into()),
+        );
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        let list = Data::List(vec![
+            Data::Text("foobar".into()),
+            Data::Text("foobaz".into()),
+            Data::Text("fooqux".into()),
+        ]);
+        test_transformer(
+            &Transformer::CommonPrefix { substring: false },
+            list,
+            Data::Text("foo".into()),
+        );
+        test_transformer(
+            &Transformer::CommonPrefix { substring: true },
+            Data::List(vec![
+                Data::Text("xbarfoo".into()),
+                Data::Text("foobarf".into()),
+                Data::Text("abarf".into()),
+            ]),
+            Data::Text("barf".into()),
+        );
+    }
+
+    #[test]
+    fn test_ip_format() {
+        test_transformer(
+            &Transformer::IpFormat {
+                to: IpFormat::Integer,
+            },
+            Data::Text("192.168.0.1".into()),
+            Data::Text("3232235521".into()),
+        );
+        test_transformer(
+            &Transformer::IpFormat { to: IpFormat::Hex },
+            Data::Text("3232235521".into()),
+            Data::Text("0xc0a80001".into()),
+        );
+        test_transformer(
+            &Transformer::IpFormat {
+                to: IpFormat::Dotted,
+            },
+            Data::Text("0xC0A80001".into()),
+            Data::Text("192.168.0.1".into()),
+        );
+        test_transformer(
+            &Transformer::IpFormat {
+                to: IpFormat::Integer,
+            },
+            Data::Text("256.0.0.1".into()),
+            Data::Error("Not an IPv4 address".into()),
+        );
+    }
+
+    #[test]
+    fn test_eval() {
+        test_transformer(
+            &Transformer::Eval,
+            Data::Text("2 + 3 * 4".into()),
+            Data::Text("14".into()),
+        );
+        test_transformer(
+            &Transformer::Eval,
+            Data::Text("-(1.5 + 2) * 2 / 7".into()),
+            Data::Text("-1".into()),
+        );
+        test_transformer(
+            &Transformer::Eval,
+            Data::Text("1 / (2 - 2)".into()),
+            Data::Error("Division by zero".into()),
+        );
+        test_transformer(
+            &Transformer::Eval,
+            Data::Text("2 +".into()),
+            Data::Error("Unexpected end of expression".into()),
+        );
+        test_transformer(
+            &Transformer::Eval,
+            Data::Text("(".repeat(100_000)),
+            Data::Error("Expression is nested too deeply".into()),
+        );
+    }
+
+    #[test]
+    fn test_translate() {
+        test_transformer(
+            &Transformer::Translate {
+                from: "abc".into(),
+                to: "xyz".into(),
+            },
+            Data::Text("cab!".into()),
+            Data::Text("zxy!".into()),
+        );
+        test_transformer(
+            &Transformer::Translate {
+                from: "abc".into(),
+                to: "xy".into(),
+            },
+            Data::Text("cab".into()),
+            Data::Error("From and to differ in length".into()),
+        );
+    }
+
+    #[test]
+    fn test_unescape() {
+        test_transformer(
+            &Transformer::Unescape { escape: false },
+            Data::Text(r"a\nb\t\\\q".into()),
+            Data::Text("a\nb\t\\\\q".into()),
+        );
+        test_transformer(
+            &Transformer::Unescape { escape: true },
+            Data::Text("a\nb\t\\".into()),
+            Data::Text(r"a\nb\t\\".into()),
+        );
+    }
+
+    #[test]
+    fn test_length_breakdown() {
+        // Woman, zero-width joiner, laptop
+        test_transformer(
+            &Transformer::LengthBreakdown,
+            Data::Text("a👩\u{200d}💻".into()),
+            Data::List(vec![
+                Data::Text("bytes: 12".into()),
+                Data::Text("chars: 4".into()),
+                Data::Text("graphemes: 2".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_split_graphemes() {
+        // The flag is two regional indicator symbols
+        test_transformer(
+            &Transformer::SplitGraphemes,
+            Data::Text("a🇫🇷e\u{301}".into()),
+            Data::List(vec![
+                Data::Text("a".into()),
+                Data::Text("🇫🇷".into()),
+                Data::Text("e\u{301}".into()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_base_convert() {
+        let transformer = Transformer::BaseConvert { from: 16, to: 10 };
+        test_transformer(
+            &transformer,
+            Data::List(vec![Data::Text("ff".into()), Data::Text("10".into())]),
+            Data::List(vec![Data::Text("255".into()), Data::Text("16".into())]),
+        );
+        test_transformer(
+            &Transformer::BaseConvert { from: 10, to: 2 },
+            Data::Text("-5".into()),
+            Data::Text("-101".into()),
+        );
+        test_transformer(
+            &transformer,
+            Data::Text("xyz".into()),
+            Data::Error("Not a base 16 number".into()),
+        );
+    }
+
+    #[test]
+    fn test_playfair() {
+        test_transformer(
+            &Transformer::Playfair {
+                key: "playfair example".into(),
+                decrypt: false,
+            },
+            Data::Text("Hide the gold in the tree stump".into()),
+            Data::Text("BMODZBXDNABEKUDMUIXMMOUVIF".into()),
+        );
+        test_transformer(
+            &Transformer::Playfair {
+                key: "playfair example".into(),
+                decrypt: true,
+            },
+            Data::Text("BMODZBXDNABEKUDMUIXMMOUVIF".into()),
+            Data::Text("HIDETHEGOLDINTHETREXESTUMP".into()),
+        );
+        test_transformer(
+            &Transformer::Playfair {
+                key: "playfair example".into(),
+                decrypt: true,
+            },
+            Data::Text("BMO".into()),
+            Data::Error("Odd number of letters".into()),
+        );
+    }
+
+    #[test]
+    fn test_a1z26() {
+        test_transformer(
+            &Transformer::A1Z26 { decode: false },
+            Data::Text("abc".into()),
+            Data::Text("1 2 3".into()),
+        );
+        test_transformer(
+            &Transformer::A1Z26 { decode: true },
+            Data::Text("1 2 3".into()),
+            Data::Text("abc".into()),
+        );
+        test_transformer(
+            &Transformer::A1Z26 { decode: false },
+            Data::Text("Hi there!".into()),
+            Data::Text("8 9 - 20 8 5 18 5 !".into()),
+        );
+        test_transformer(
+            &Transformer::A1Z26 { decode: true },
+            Data::Text("8-9 - 20-8-5-18-5 !".into()),
+            Data::Text("hi there!".into()),
+        );
+        test_transformer(
+            &Transformer::A1Z26 { decode: true },
+            Data::Text("27".into()),
+            Data::Error("27 is not a letter".into()),
+        );
+    }
+
+    #[test]
+    fn test_between() {
+        let input = Data::Text("a [b] c [d] [e".into());
+        test_transformer(
+            &Transformer::Between {
+                start: "[".into(),
+                end: "]".into(),
+                all: false,
+            },
+            input.clone(),
+            Data::Text("b".into()),
+        );
+        test_transformer(
+            &Transformer::Between {
+                start: "[".into(),
+                end: "]".into(),
+                all: true,
+            },
+            input,
+            Data::List(vec![Data::Text("b".into()), Data::Text("d".into())]),
+        );
+        test_transformer(
+            &Transformer::Between {
+                start: "<".into(),
+                end: ">".into(),
+                all: false,
+            },
+            Data::Text("a [b]".into()),
+            Data::Error("Delimiters not found".into()),
+        );
+    }
+
+    #[test]
+    fn test_tap_code() {
+        let encoded = Data::Text(".. ...  . .....  ... .  ... .  ... ....".into());
+        test_transformer(
+            &Transformer::TapCode { decode: false },
+            Data::Text("Hello".into()),
+            encoded.clone(),
+        );
+        test_transformer(
+            &Transformer::TapCode { decode: true },
+            encoded,
+            Data::Text("hello".into()),
+        );
+        test_transformer(
+            &Transformer::TapCode { decode: true },
+            Transformer::TapCode { decode: false }.transform(&Data::Text("knock knock".into())),
+            Data::Text("cnocc cnocc".into()),
+        );
+        test_transformer(
+            &Transformer::TapCode { decode: true },
+            Data::Text(".. ... ..".into()),
+            Data::Error("Unpaired taps".into()),
+        );
+    }
+
+    #[test]
+    fn test_mod_exp() {
+        test_transformer(
+            &Transformer::ModExp {
+                base: "4".into(),
+                exp: "13".into(),
+                modulus: "497".into(),
+            },
+            Data::Text(String::new()),
+            Data::Text("445".into()),
+        );
+        // Toy RSA: n = 61 * 53, e = 17, d = 2753
+        test_transformer(
+            &Transformer::ModExp {
+                base: String::new(),
+                exp: "2753".into(),
+                modulus: "3233".into(),
+            },
+            Data::Text("2790".into()),
+            Data::Text("65".into()),
+        );
+        test_transformer(
+            &Transformer::ModExp {
+                base: "2".into(),
+                exp: "3".into(),
+                modulus: "0".into(),
+            },
+            Data::Text(String::new()),
+            Data::Error("Modulus can't be zero".into()),
+        );
+    }
+
+    #[test]
+    fn test_factorize() {
+        test_transformer(
+            &Transformer::Factorize,
+            Data::Text("360".into()),
+            Data::List(
+                ["2", "2", "2", "3", "3", "5"]
+                    .into_iter()
+                    .map(|f| Data::Text(f.into()))
+                    .collect(),
+            ),
+        );
+        test_transformer(
+            &Transformer::Factorize,
+            Data::Text("18446744073709551557".into()),
+            Data::Error("Too large to factor".into()),
+        );
+        test_transformer(
+            &Transformer::Factorize,
+            Data::Text("0".into()),
+            Data::Error("0 has no prime factors".into()),
+        );
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let numbers = Data::List(
+            ["12", "18", "24"]
+                .into_iter()
+                .map(|n| Data::Text(n.into()))
+                .collect(),
+        );
+        test_transformer(
+            &Transformer::GcdLcm { lcm: false },
+            numbers.clone(),
+            Data::Text("6".into()),
+        );
+        test_transformer(
+            &Transformer::GcdLcm { lcm: true },
+            numbers,
+            Data::Text("72".into()),
+        );
+        test_transformer(
+            &Transformer::GcdLcm { lcm: false },
+            Data::List(vec![Data::Text("12".into()), Data::Text("x".into())]),
+            Data::Error("x is not a whole number".into()),
+        );
+    }
+
+    #[test]
+    fn test_regional_indicators() {
+        test_transformer(
+            &Transformer::RegionalIndicators { decode: false },
+            Data::Text("us!".into()),
+            Data::Text("🇺🇸!".into()),
+        );
+        test_transformer(
+            &Transformer::RegionalIndicators { decode: true },
+            Data::Text("🇺🇸!".into()),
+            Data::Text("US!".into()),
+        );
+    }
+
+    #[test]
+    fn test_printf() {
+        let printf = |spec: &str, input: &str, expected: Data| {
+            test_transformer(
+                &Transformer::Printf { spec: spec.into() },
+                Data::Text(input.into()),
+                expected,
+            )
+        };
+        printf("%05.2f", "3.1", Data::Text("03.10".into()));
+        printf("%+.1f%%", "-2.25", Data::Text("-2.2%".into()));
+        printf("0x%04X", "255", Data::Text("0x00FF".into()));
+        printf("[%-4d]", "42", Data::Text("[42  ]".into()));
+        printf("%b", "5", Data::Text("101".into()));
+        printf("%d", "abc", Data::Error("Not a whole number".into()));
+        printf("%q", "1", Data::Error("Unsupported conversion 'q'".into()));
+        printf(
+            "%9999999999999d",
+            "1",
+            Data::Error("Width too large".into()),
+        );
+        printf(
+            "%.9999999999f",
+            "1",
+            Data::Error("Precision too large".into()),
+        );
+        printf(
+            "%99999999999999999999999d",
+            "1",
+            Data::Error("Width too large".into()),
+        );
+    }
+
+    #[test]
+    fn test_accepts() {
+        let list = Data::List(vec![Data::Text("a".into())]);
+        let text = Data::Text("a".into());
+        for transformer in [
+            Transformer::Join {
+                separator: String::new(),
+                prefix: String::new(),
+                suffix: String::new(),
+            },
+            Transformer::CommonPrefix { substring: false },
+            Transformer::GcdLcm { lcm: false },
+            Transformer::NaturalSort { descending: false },
+            Transformer::Markdown {
+                kind: MdKind::Bullets,
+            },
+        ] {
+            assert_eq!(transformer.accepts(), InputKind::List);
+        }
+        for transformer in [
+            Transformer::Range {
+                start: 1,
+                end: 10,
+                step: 1,
+            },
+            Transformer::Sequence {
+                kind: SeqKind::Fibonacci,
+                count: 10,
+            },
+            Transformer::Permutations { k: None },
+            Transformer::Acrostic {
+                position: AcrosticPos::First,
+            },
+            Transformer::Encode {
+                encoding: Encoding::Base64,
+            },
+            Transformer::Checksum {
+                algo: ChecksumAlgo::Crc32,
+            },
+            Transformer::Hmac {
+                algo: HashAlgorithm::Sha256,
+                key: String::new(),
+            },
+            Transformer::Hash {
+                algo: HashAlgorithm::Sha256,
+            },
+            Transformer::Base64Custom {
+                alphabet: String::new(),
+                decode: false,
+            },
+            Transformer::GuessXorKeyLen { max: 40 },
+            Transformer::SingleByteXor,
+            Transformer::DecodeBinaryFormat {
+                fmt: BinFmt::MessagePack,
+            },
+            Transformer::ImageInfo,
+            Transformer::Strings { min_len: 4 },
+            Transformer::Not,
+            Transformer::Bom { add: false },
+            Transformer::DetectCharset,
+            Transformer::Transcode {
+                from: CharsetName::Windows1252,
+            },
+        ] {
+            assert_eq!(transformer.accepts(), InputKind::Any);
+        }
+        for transformer in [
+            Transformer::Split {
+                pattern: String::new(),
+            },
+            Transformer::Find {
+                pattern: String::new(),
+                flatten: false,
+            },
+            Transformer::Replace {
+                pattern: String::new(),
+                replacer: String::new(),
+            },
+            Transformer::Slice { from: 0, to: 0 },
+            Transformer::Decode {
+                encoding: Encoding::Base64,
+            },
+            Transformer::Uppercase,
+            Transformer::Lowercase,
+            Transformer::FormDecode,
+            Transformer::StripHtml {
+                decode_entities: true,
+            },
+            Transformer::NumberFormat {
+                thousands: true,
+                decimals: None,
+            },
+            Transformer::MultiReplace { rules: Vec::new() },
+            Transformer::SortChars { descending: false },
+            Transformer::UnixTime { to_date: true },
+            Transformer::SwapEndian { word_size: 4 },
+            Transformer::Bacon { decode: false },
+            Transformer::ExtractEntities {
+                kind: EntityKind::Url,
+            },
+            Transformer::Rotations,
+            Transformer::RegexEscape,
+            Transformer::Math {
+                func: MathFunc::Sqrt,
+            },
+            Transformer::Caesar {
+                shift: 13,
+                digits: false,
+            },
+            Transformer::ConfigConvert {
+                from: ConfigFmt::Json,
+                to: ConfigFmt::Yaml,
+            },
+            Transformer::PartialUrlDecode {
+                escapes: "%20".into(),
+            },
+            Transformer::CollapseWhitespace,
+            Transformer::FixedWidth { widths: Vec::new() },
+            Transformer::Luhn { verify: true },
+            Transformer::GrayCode { to_gray: true },
+            Transformer::RunLength { decode: false },
+            Transformer::IpFormat {
+                to: IpFormat::Integer,
             },
-            Data::List(data_vec) => match self {
-                Transformer::Join { separator } => {
-                    let mut texts = Vec::new();
-                    fn collect(d: &Data, sep: &str, out: &mut Vec<String>) -> Option<String> {
-                        match d {
-                            Data::Text(t) => {
-                                out.push(t.into());
-                                None
-                            }
-                            Data::Error(_) => Some("Input error".into()),
-                            Data::List(list) => {
-                                for item in list {
-                                    if let Some(err) = collect(item, sep, out) {
-                                        return Some(err);
-                                    }
-                                }
-                                None
-                            }
-                        }
-                    }
-                    if let Some(err) = collect(data, separator, &mut texts) {
-                        Data::Error(err)
-                    } else {
-                        Data::Text(texts.join(separator))
-                    }
-                }
-                _ => Data::List(data_vec.iter().map(|d| self.transform(&d)).collect()),
+            Transformer::Eval,
+            Transformer::Translate {
+                from: String::new(),
+                to: String::new(),
             },
-            Data::Error(_) => Data::Error("Input error".into()),
+            Transformer::Unescape { escape: false },
+            Transformer::LengthBreakdown,
+            Transformer::SplitGraphemes,
+            Transformer::BaseConvert { from: 16, to: 10 },
+            Transformer::Playfair {
+                key: String::new(),
+                decrypt: true,
+            },
+            Transformer::A1Z26 { decode: true },
+            Transformer::Between {
+                start: "[".into(),
+                end: "]".into(),
+                all: true,
+            },
+            Transformer::TapCode { decode: true },
+            Transformer::ModExp {
+                base: String::new(),
+                exp: "65537".into(),
+                modulus: String::new(),
+            },
+            Transformer::Factorize,
+            Transformer::RegionalIndicators { decode: true },
+            Transformer::Printf { spec: "%d".into() },
+            Transformer::SwapNibbles,
+            Transformer::ColumnarTransposition {
+                key: String::new(),
+                decrypt: true,
+            },
+            Transformer::Leet { decode: true },
+            Transformer::Duration {
+                to: DurationFmt::Human,
+            },
+            Transformer::ParseKeyValue {
+                line_sep: String::new(),
+                kv_sep: "=".into(),
+            },
+            Transformer::MockCase { seed: 0 },
+            Transformer::Line {
+                index: 1,
+                from_end: false,
+            },
+            Transformer::SortLines {
+                descending: false,
+                numeric: false,
+            },
+            Transformer::UniqueLines {
+                adjacent_only: false,
+            },
+            Transformer::Grep {
+                pattern: String::new(),
+                invert: false,
+            },
+            Transformer::Substitution {
+                cipher_alphabet: "abcdefghijklmnopqrstuvwxyz".into(),
+                decrypt: true,
+            },
+            Transformer::Base64Custom {
+                alphabet: String::new(),
+                decode: true,
+            },
+        ] {
+            assert_eq!(transformer.accepts(), InputKind::Text);
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert!(InputKind::List.accepts(&list));
+        assert!(!InputKind::List.accepts(&text));
+        assert!(InputKind::List.accepts(&Data::Error("Input error".into())));
+        assert!(InputKind::Text.accepts(&text));
+        assert!(InputKind::Text.accepts(&list));
+        assert!(!InputKind::Text.accepts(&Data::Bytes(vec![0xFF])));
+        assert!(!InputKind::Text.accepts(&Data::List(vec![Data::Bytes(vec![0xFF])])));
+        assert!(InputKind::Any.accepts(&Data::Bytes(vec![0xFF])));
+    }
 
     #[test]
-    fn test_split() {
-        let transformer = Transformer::Split {
-            pattern: " ".into(),
-        };
-
+    fn test_swap_nibbles() {
         test_transformer(
-            &transformer,
-            Data::Text("Sample Text".into()),
-            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
+            &Transformer::SwapNibbles,
+            Data::Text("1234".into()),
+            Data::Text("2143".into()),
         );
+        test_transformer(
+            &Transformer::SwapNibbles,
+            Data::Text("123".into()),
+            Data::Error("Odd number of hex digits".into()),
+        );
+    }
 
+    #[test]
+    fn test_columnar_transposition() {
+        let plaintext = Data::Text("WEAREDISCOVEREDFLEEATONCE".into());
+        let ciphertext = Data::Text("EVLNACDTESEAROFODEECWIREE".into());
         test_transformer(
-            &transformer,
-            Data::List(vec![
-                Data::Text("Sample Text".into()),
-                Data::Text("Another Sample Text".into()),
-            ]),
-            Data::List(vec![
-                Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
-                Data::List(vec![
-                    Data::Text("Another".into()),
-                    Data::Text("Sample".into()),
-                    Data::Text("Text".into()),
-                ]),
-            ]),
+            &Transformer::ColumnarTransposition {
+                key: "ZEBRAS".into(),
+                decrypt: false,
+            },
+            plaintext.clone(),
+            ciphertext.clone(),
+        );
+        test_transformer(
+            &Transformer::ColumnarTransposition {
+                key: "ZEBRAS".into(),
+                decrypt: true,
+            },
+            ciphertext,
+            plaintext,
         );
     }
 
     #[test]
-    fn test_join() {
-        let transformer = Transformer::Join {
-            separator: " ".into(),
-        };
+    fn test_leet() {
+        test_transformer(
+            &Transformer::Leet { decode: false },
+            Data::Text("Leet".into()),
+            Data::Text("1337".into()),
+        );
+        test_transformer(
+            &Transformer::Leet { decode: true },
+            Data::Text("p4$$w0rd".into()),
+            Data::Text("password".into()),
+        );
+    }
 
+    #[test]
+    fn test_duration() {
         test_transformer(
-            &transformer,
-            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
-            Data::Text("Sample Text".into()),
+            &Transformer::Duration {
+                to: DurationFmt::Human,
+            },
+            Data::Text("3723".into()),
+            Data::Text("1h2m3s".into()),
+        );
+        test_transformer(
+            &Transformer::Duration {
+                to: DurationFmt::Seconds,
+            },
+            Data::Text("1d 1m".into()),
+            Data::Text("86460".into()),
+        );
+        test_transformer(
+            &Transformer::Duration {
+                to: DurationFmt::Seconds,
+            },
+            Data::Text("1h2x".into()),
+            Data::Error("Not a duration".into()),
         );
+    }
 
+    #[test]
+    fn test_parse_key_value() {
         test_transformer(
-            &transformer,
+            &Transformer::ParseKeyValue {
+                line_sep: String::new(),
+                kv_sep: "=".into(),
+            },
+            Data::Text("[server]\nhost = example.com\nport=8080\n; comment\n".into()),
             Data::List(vec![
-                Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
-                Data::List(vec![
-                    Data::Text("Another".into()),
-                    Data::Text("Sample".into()),
-                    Data::Text("Text".into()),
-                ]),
+                Data::Text("host=example.com".into()),
+                Data::Text("port=8080".into()),
             ]),
-            Data::Text("Sample Text Another Sample Text".into()),
+        );
+        test_transformer(
+            &Transformer::ParseKeyValue {
+                line_sep: "&".into(),
+                kv_sep: "=".into(),
+            },
+            Data::Text("a=1&b=2".into()),
+            Data::List(vec![Data::Text("a=1".into()), Data::Text("b=2".into())]),
         );
     }
 
     #[test]
-    fn test_find() {
-        let transformer = Transformer::Find {
-            pattern: "Text".into(),
+    fn test_mock_case() {
+        let input = Data::Text("the quick brown fox jumps over the lazy dog".into());
+        let mock = |seed| Transformer::MockCase { seed }.transform(&input);
+        let Data::Text(mocked) = mock(42) else {
+            panic!("not a text");
         };
+        assert_eq!(mock(42), Data::Text(mocked.clone()));
+        assert_ne!(mock(43), Data::Text(mocked.clone()));
+        assert_eq!(Data::Text(mocked.to_lowercase()), input);
+        assert_ne!(Data::Text(mocked), input);
+    }
 
+    #[test]
+    fn test_line() {
+        let input = Data::Text("one\ntwo\nthree".into());
         test_transformer(
-            &transformer,
-            Data::Text("Sample Text".into()),
-            Data::List(vec![Data::Text("Text".into())]),
+            &Transformer::Line {
+                index: 2,
+                from_end: false,
+            },
+            input.clone(),
+            Data::Text("two".into()),
         );
+        test_transformer(
+            &Transformer::Line {
+                index: 1,
+                from_end: true,
+            },
+            input.clone(),
+            Data::Text("three".into()),
+        );
+        test_transformer(
+            &Transformer::Line {
+                index: 4,
+                from_end: false,
+            },
+            input,
+            Data::Error("No line 4 in 3 lines".into()),
+        );
+    }
 
+    #[test]
+    fn test_sort_lines() {
+        let input = Data::Text("10\n2\n1".into());
         test_transformer(
-            &transformer,
-            Data::List(vec![
-                Data::Text("Sample Text".into()),
-                Data::Text("Another Sample Text".into()),
-            ]),
-            Data::List(vec![
-                Data::List(vec![Data::Text("Text".into())]),
-                Data::List(vec![Data::Text("Text".into())]),
-            ]),
+            &Transformer::SortLines {
+                descending: false,
+                numeric: true,
+            },
+            input.clone(),
+            Data::Text("1\n2\n10".into()),
+        );
+        test_transformer(
+            &Transformer::SortLines {
+                descending: true,
+                numeric: true,
+            },
+            input.clone(),
+            Data::Text("10\n2\n1".into()),
+        );
+        test_transformer(
+            &Transformer::SortLines {
+                descending: false,
+                numeric: false,
+            },
+            input,
+            Data::Text("1\n10\n2".into()),
         );
     }
 
     #[test]
-    fn test_replace() {
-        let transformer = Transformer::Replace {
-            pattern: "Sample".into(),
-            replacer: "Test".into(),
-        };
+    fn test_unique_lines() {
+        let input = Data::Text("a\na\nb\na".into());
+        test_transformer(
+            &Transformer::UniqueLines {
+                adjacent_only: false,
+            },
+            input.clone(),
+            Data::Text("a\nb".into()),
+        );
+        test_transformer(
+            &Transformer::UniqueLines {
+                adjacent_only: true,
+            },
+            input,
+            Data::Text("a\nb\na".into()),
+        );
+    }
 
+    #[test]
+    fn test_grep() {
+        let input = Data::Text("abc\na1\nxyz\n42".into());
         test_transformer(
-            &transformer,
-            Data::Text("Sample Text".into()),
-            Data::Text("Test Text".into()),
+            &Transformer::Grep {
+                pattern: r"\d".into(),
+                invert: false,
+            },
+            input.clone(),
+            Data::Text("a1\n42".into()),
+        );
+        test_transformer(
+            &Transformer::Grep {
+                pattern: r"\d".into(),
+                invert: true,
+            },
+            input,
+            Data::Text("abc\nxyz".into()),
         );
+    }
 
+    #[test]
+    fn test_natural_sort() {
+        let list =
+            |items: &[&str]| Data::List(items.iter().map(|i| Data::Text(i.to_string())).collect());
         test_transformer(
-            &transformer,
-            Data::List(vec![
-                Data::Text("Sample Text".into()),
-                Data::Text("Another Sample Text".into()),
-            ]),
+            &Transformer::NaturalSort { descending: false },
+            list(&["img10", "img2", "img1", "a"]),
+            list(&["a", "img1", "img2", "img10"]),
+        );
+        test_transformer(
+            &Transformer::NaturalSort { descending: true },
+            list(&["img2", "img10", "img02b"]),
+            list(&["img10", "img02b", "img2"]),
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let list =
+            |items: &[&str]| Data::List(items.iter().map(|i| Data::Text(i.to_string())).collect());
+        test_transformer(
+            &Transformer::Markdown {
+                kind: MdKind::Bullets,
+            },
+            list(&["one", "two"]),
+            Data::Text("- one\n- two".into()),
+        );
+        test_transformer(
+            &Transformer::Markdown {
+                kind: MdKind::Numbered,
+            },
+            list(&["one", "two"]),
+            Data::Text("1. one\n2. two".into()),
+        );
+        test_transformer(
+            &Transformer::Markdown {
+                kind: MdKind::Table,
+            },
             Data::List(vec![
-                Data::Text("Test Text".into()),
-                Data::Text("Another Test Text".into()),
+                list(&["name", "value"]),
+                list(&["a|b", "1"]),
+                list(&["c"]),
             ]),
+            Data::Text("| name | value |\n| --- | --- |\n| a\\|b | 1 |\n| c |  |".into()),
         );
     }
 
     #[test]
-    fn test_slice() {
-        let transformer = Transformer::Slice { from: 1, to: 6 };
+    fn test_substitution() {
+        let cipher_alphabet = "QWERTYUIOPASDFGHJKLZXCVBNM".to_string();
+        let encrypted = Data::Text("Itssg, Vgksr!".into());
+        test_transformer(
+            &Transformer::Substitution {
+                cipher_alphabet: cipher_alphabet.clone(),
+                decrypt: false,
+            },
+            Data::Text("Hello, World!".into()),
+            encrypted.clone(),
+        );
+        test_transformer(
+            &Transformer::Substitution {
+                cipher_alphabet,
+                decrypt: true,
+            },
+            encrypted,
+            Data::Text("Hello, World!".into()),
+        );
+        test_transformer(
+            &Transformer::Substitution {
+                cipher_alphabet: "ABCDEFGHIJKLMNOPQRSTUVWXYA".into(),
+                decrypt: false,
+            },
+            Data::Text("abc".into()),
+            Data::Error("Cipher alphabet must have each letter once".into()),
+        );
+    }
 
+    #[test]
+    fn test_bom() {
         test_transformer(
-            &transformer,
-            Data::Text("Sample Text".into()),
-            Data::Text("ample".into()),
+            &Transformer::Bom { add: false },
+            Data::Text("\u{FEFF}text".into()),
+            Data::Text("text".into()),
+        );
+        test_transformer(
+            &Transformer::Bom { add: true },
+            Data::Text("text".into()),
+            Data::Text("\u{FEFF}text".into()),
+        );
+        test_transformer(
+            &Transformer::Bom { add: false },
+            Data::Bytes(vec![0xEF, 0xBB, 0xBF, 0xFF]),
+            Data::Bytes(vec![0xFF]),
         );
+    }
 
+    #[test]
+    fn test_hash() {
         test_transformer(
-            &transformer,
+            &Transformer::Hash {
+                algo: HashAlgorithm::Sha256,
+            },
+            Data::List(vec![Data::Text("a".into()), Data::Text("b".into())]),
             Data::List(vec![
-                Data::Text("Sample Text".into()),
-                Data::Text("Another Sample Text".into()),
+                Data::Text(
+                    "ca978112ca1bbdcafac231b39a23dc4da786eff8147c4e72b9807785afee48bb".into(),
+                ),
+                Data::Text(
+                    "3e23e8160039594a33894f6564e1b1348bbd7a0088d42c4acb73eeaed59c009d".into(),
+                ),
             ]),
-            Data::List(vec![Data::Text("ample".into()), Data::Text("nothe".into())]),
+        );
+        test_transformer(
+            &Transformer::Hash {
+                algo: HashAlgorithm::Sha1,
+            },
+            Data::Bytes(vec![0xFF]),
+            Data::Text("85e53271e14006f0265921d02d4d736cdc580b0b".into()),
         );
     }
 
-    // TODO: encode and decode tests
-
     #[test]
-    fn test_uppercase() {
-        let transformer = Transformer::Uppercase;
-
+    fn test_punycode() {
         test_transformer(
-            &transformer,
-            Data::Text("Sample Text".into()),
-            Data::Text("SAMPLE TEXT".into()),
+            &Transformer::Encode {
+                encoding: Encoding::Punycode,
+            },
+            Data::Text("münchen".into()),
+            Data::Text("xn--mnchen-3ya".into()),
+        );
+        test_transformer(
+            &Transformer::Decode {
+                encoding: Encoding::Punycode,
+            },
+            Data::Text("xn--mnchen-3ya.de".into()),
+            Data::Text("münchen.de".into()),
+        );
+        test_transformer(
+            &Transformer::Decode {
+                encoding: Encoding::Punycode,
+            },
+            Data::Text("xn--a.de".into()),
+            Data::Error("Not a valid domain name".into()),
         );
+    }
 
+    #[test]
+    fn test_acrostic() {
+        let words = Data::List(
+            ["Apple", "Banana", "Cat"]
+                .into_iter()
+                .map(|w| Data::Text(w.into()))
+                .collect(),
+        );
         test_transformer(
-            &transformer,
-            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
-            Data::List(vec![Data::Text("SAMPLE".into()), Data::Text("TEXT".into())]),
+            &Transformer::Acrostic {
+                position: AcrosticPos::First,
+            },
+            words.clone(),
+            Data::Text("ABC".into()),
+        );
+        test_transformer(
+            &Transformer::Acrostic {
+                position: AcrosticPos::Last,
+            },
+            words.clone(),
+            Data::Text("eat".into()),
+        );
+        test_transformer(
+            &Transformer::Acrostic {
+                position: AcrosticPos::Nth(4),
+            },
+            words,
+            Data::Text("la".into()),
+        );
+        test_transformer(
+            &Transformer::Acrostic {
+                position: AcrosticPos::First,
+            },
+            Data::Text("Hidden\nInside".into()),
+            Data::Text("HI".into()),
         );
     }
 
     #[test]
-    fn test_lowercase() {
-        let transformer = Transformer::Lowercase;
+    fn test_image_info() {
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, 2, 3);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0; 6]).unwrap();
+        writer.finish().unwrap();
 
+        let expected = Data::List(vec![
+            Data::Text("format=PNG".into()),
+            Data::Text("width=2".into()),
+            Data::Text("height=3".into()),
+        ]);
         test_transformer(
-            &transformer,
-            Data::Text("Sample Text".into()),
-            Data::Text("sample text".into()),
+            &Transformer::ImageInfo,
+            Data::Bytes(png.clone()),
+            expected.clone(),
+        );
+        test_transformer(
+            &Transformer::ImageInfo,
+            Data::Text(BASE64_STANDARD.encode(&png)),
+            expected,
+        );
+        test_transformer(
+            &Transformer::ImageInfo,
+            Data::Bytes(vec![0xFF, 0x00]),
+            Data::Error("Not a PNG, JPEG or GIF image".into()),
         );
+    }
 
+    #[test]
+    fn test_strings() {
+        let mut bytes = vec![0x00, 0xFF, b'a', b'b', 0x01];
+        bytes.extend(b"hidden flag");
+        bytes.extend([0x00, 0x89]);
+        bytes.extend(b"end");
         test_transformer(
-            &transformer,
-            Data::List(vec![Data::Text("Sample".into()), Data::Text("Text".into())]),
-            Data::List(vec![Data::Text("sample".into()), Data::Text("text".into())]),
+            &Transformer::Strings { min_len: 3 },
+            Data::Bytes(bytes),
+            Data::List(vec![
+                Data::Text("hidden flag".into()),
+                Data::Text("end".into()),
+            ]),
         );
     }
 